@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use crate::{parse_number, Day, ParseError, Part};
+
+/// Default location of the recorded answers, relative to the crate root.
+pub const ANSWERS_PATH: &str = "answers.toml";
+
+/// Known-good answers for the real `inputs/dayX.txt` puzzle inputs.
+///
+/// The file is a small TOML subset: one `[dayX]` table per day holding
+/// `part1` and `part2` keys, with either quoted or bare integer values.
+#[derive(Debug, Default)]
+pub struct Answers(HashMap<(u32, Part), String>);
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut answers = Answers::default();
+        let mut day: Option<u32> = None;
+
+        for (line_index, raw_line) in input.lines().enumerate() {
+            let line = raw_line.split('#').next().unwrap().trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let number = table.strip_prefix("day").ok_or_else(|| {
+                    ParseError::new(line_index, raw_line, table, "expected '[dayX]'")
+                })?;
+
+                day = Some(parse_number(line_index, raw_line, number)?);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .ok_or_else(|| {
+                    ParseError::new(line_index, raw_line, line, "expected 'key = value'")
+                })?;
+
+            let part = match key {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => {
+                    return Err(ParseError::new(
+                        line_index,
+                        raw_line,
+                        key,
+                        "expected 'part1' or 'part2'",
+                    ))
+                }
+            };
+
+            let value = match value.strip_prefix('"') {
+                Some(quoted) => quoted.strip_suffix('"').ok_or_else(|| {
+                    ParseError::new(line_index, raw_line, value, "unterminated string")
+                })?,
+                None => {
+                    parse_number::<u128>(line_index, raw_line, value)?;
+                    value
+                }
+            };
+
+            let day = day.ok_or_else(|| {
+                ParseError::new(
+                    line_index,
+                    raw_line,
+                    key,
+                    "answer outside of a '[dayX]' table",
+                )
+            })?;
+
+            answers.0.insert((day, part), value.to_string());
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// Outcome of comparing a freshly computed answer with the recorded one.
+#[derive(Debug)]
+pub struct Verdict {
+    pub day: u32,
+    pub part: Part,
+    pub expected: Option<String>,
+    pub actual: String,
+}
+
+impl Verdict {
+    pub fn is_match(&self) -> bool {
+        self.expected.as_ref() == Some(&self.actual)
+    }
+}
+
+pub fn verify(
+    answers: &Answers,
+    day: &Day,
+    input: &str,
+    parts: &[Part],
+) -> Result<Vec<Verdict>, ParseError> {
+    let solved = (day.solve)(input, parts)?;
+
+    Ok(solved
+        .answers
+        .into_iter()
+        .map(|answer| Verdict {
+            day: day.number,
+            part: answer.part,
+            expected: answers.get(day.number, answer.part).map(String::from),
+            actual: answer.value,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_input, DAYS};
+
+    #[test]
+    fn parse_test() {
+        let answers = Answers::parse("[day1]\npart1 = 142 # example\npart2 = \"281\"\n").unwrap();
+
+        assert_eq!(answers.get(1, Part::One), Some("142"));
+        assert_eq!(answers.get(1, Part::Two), Some("281"));
+        assert_eq!(answers.get(2, Part::One), None);
+
+        let error = Answers::parse("[day1]\npart3 = 1\n").unwrap_err();
+
+        assert_eq!((error.line, error.snippet.as_str()), (2, "part3"));
+    }
+
+    #[test]
+    fn verify_test() {
+        let answers = Answers::parse(&read_input(ANSWERS_PATH).unwrap()).unwrap();
+
+        for day in DAYS.iter() {
+            let input = read_input(&day.input_path()).unwrap();
+
+            for verdict in verify(&answers, day, &input, &Part::BOTH).unwrap() {
+                assert!(verdict.is_match(), "{:?}", verdict);
+            }
+        }
+    }
+}
@@ -1,107 +1,125 @@
-use std::fs;
+use std::io;
 
-type Day1 = Vec<String>;
+use aoc23::{
+    cli::{exit_on_error, load, load_with, read_error, DayArgs},
+    day1::{calibrate, calibrate_stream, Calibrator, Day1, NoDigits, Vocabulary},
+    input_name, open_input, ParseError,
+};
 
 fn main() {
-    let input = read_input("inputs/day1.txt");
-    let p1_result = puzzle1(&input);
-    let p2_result = puzzle2(&input);
-
-    println!("Puzzle #1: {}", p1_result);
-    println!("Puzzle #2: {}", p2_result);
-}
-
-fn read_input(path: &str) -> Day1 {
-    let input = fs::read_to_string(path).unwrap();
+    let args = DayArgs::from_env(
+        "inputs/day1.txt",
+        &["--stream"],
+        &["--vocabulary", "--progress", "--no-digits"],
+    );
+
+    let policy = exit_on_error(
+        args.option("--no-digits")
+            .map_or(Ok(NoDigits::default()), str::parse),
+    );
+
+    let calibrations = match args.option("--vocabulary") {
+        Some(path) => vec![(
+            "Calibration",
+            exit_on_error(load_with(path, Vocabulary::parse)),
+        )],
+        None => vec![
+            ("Puzzle #1", Vocabulary::new()),
+            ("Puzzle #2", Vocabulary::english()),
+        ],
+    };
+
+    let calibrators = if args.flag("--stream") {
+        stream(&args, &calibrations, policy)
+    } else {
+        let input = exit_on_error(load::<Day1>(&args.input));
+
+        exit_on_error(
+            calibrations
+                .iter()
+                .map(|(_, vocabulary)| calibrate(&input, vocabulary, policy))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| err.with_file(input_name(&args.input)).to_string()),
+        )
+    };
+
+    for ((label, _), calibrator) in calibrations.iter().zip(&calibrators) {
+        println!("{}: {}", label, calibrator.total());
+    }
 
-    input.lines().map(|l| l.to_owned()).collect()
+    for ((label, _), calibrator) in calibrations.iter().zip(&calibrators) {
+        report_missing(label, calibrator, policy);
+    }
 }
 
-fn puzzle1(input: &Day1) -> u32 {
-    input
+/// Calibrates the input line by line, printing the running totals to stderr
+/// every `--progress <lines>` lines.
+fn stream(
+    args: &DayArgs,
+    calibrations: &[(&str, Vocabulary)],
+    policy: NoDigits,
+) -> Vec<Calibrator> {
+    let progress = exit_on_error(
+        args.option("--progress")
+            .map(|every| match every.parse::<usize>() {
+                Ok(every) if every > 0 => Ok(every),
+                _ => Err("--progress expects a positive number".to_string()),
+            })
+            .transpose(),
+    );
+
+    let mut calibrators: Vec<Calibrator> = calibrations
         .iter()
-        .map(|line| {
-            let mut first_digit: Option<u32> = None;
-            let mut last_digit: u32 = 0;
+        .map(|(_, vocabulary)| Calibrator::new(vocabulary, policy))
+        .collect();
 
-            for c in line.chars() {
-                if c.is_digit(10) {
-                    let digit: u32 = c.to_digit(10).unwrap();
+    let reader =
+        exit_on_error(open_input(&args.input).map_err(|err| read_error(&args.input, &err)));
 
-                    if first_digit.is_none() {
-                        first_digit = Some(digit);
-                    }
+    let streamed = calibrate_stream(reader, &mut calibrators, |calibrators| {
+        let lines = calibrators[0].lines();
 
-                    last_digit = digit;
-                }
-            }
+        if progress.is_some_and(|every| lines % every == 0) {
+            let totals: Vec<String> = calibrators.iter().map(|c| c.total().to_string()).collect();
 
-            (first_digit.unwrap() * 10) + last_digit
-        })
-        .sum()
-}
+            eprintln!("{} lines: {}", lines, totals.join(" "));
+        }
+    });
 
-fn puzzle2(input: &Day1) -> u32 {
-    input
-        .iter()
-        .map(|line| {
-            let mut first_digit: Option<u32> = None;
-            let mut last_digit: u32 = 0;
-            let mut letter_digit: String = String::new();
-
-            for c in line.chars() {
-                let mut digit: Option<u32> = None;
-
-                if c.is_digit(10) {
-                    digit = Some(c.to_digit(10).unwrap());
-                } else {
-                    letter_digit.push(c);
-                    digit = read_letter_digit(&letter_digit);
-                }
-
-                if let Some(d) = digit {
-                    if first_digit.is_none() {
-                        first_digit = digit;
-                    }
-
-                    last_digit = d;
-                }
-            }
-
-            (first_digit.unwrap() * 10) + last_digit
-        })
-        .sum()
+    exit_on_error(streamed.map_err(|err| stream_error(&args.input, err)));
+    calibrators
 }
 
-fn read_letter_digit(letter_digit: &str) -> Option<u32> {
-    match letter_digit {
-        _ if letter_digit.ends_with("one") => Some(1),
-        _ if letter_digit.ends_with("two") => Some(2),
-        _ if letter_digit.ends_with("three") => Some(3),
-        _ if letter_digit.ends_with("four") => Some(4),
-        _ if letter_digit.ends_with("five") => Some(5),
-        _ if letter_digit.ends_with("six") => Some(6),
-        _ if letter_digit.ends_with("seven") => Some(7),
-        _ if letter_digit.ends_with("eight") => Some(8),
-        _ if letter_digit.ends_with("nine") => Some(9),
-        _ => None,
+fn stream_error(path: &str, err: io::Error) -> String {
+    match err
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<ParseError>())
+    {
+        Some(parse_error) => parse_error.clone().with_file(input_name(path)).to_string(),
+        None => read_error(path, &err),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Lists the lines without a digit on stderr, so scraped inputs can be
+/// cleaned up.
+fn report_missing(label: &str, calibrator: &Calibrator, policy: NoDigits) {
+    let missing = calibrator.missing();
 
-    #[test]
-    fn puzzle1_test() {
-        let test_input = read_input("inputs/day1_1_test.txt");
-
-        assert_eq!(puzzle1(&test_input), 142)
+    if missing.is_empty() {
+        return;
     }
 
-    #[test]
-    fn puzzle2_test() {
-        let test_input = read_input("inputs/day1_2_test.txt");
-        assert_eq!(puzzle2(&test_input), 281)
-    }
+    let lines: Vec<String> = missing.iter().map(usize::to_string).collect();
+
+    eprintln!(
+        "{}: {} line(s) without a digit {}: {}",
+        label,
+        missing.len(),
+        if policy == NoDigits::Zero {
+            "counted as 0"
+        } else {
+            "skipped"
+        },
+        lines.join(", ")
+    );
 }
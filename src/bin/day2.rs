@@ -1,144 +1,63 @@
-use std::fs;
-
-#[derive(Debug)]
-struct Game {
-    id: u32,
-    rounds: Vec<Round>,
-}
-
-#[derive(Debug)]
-struct Round {
-    red: u32,
-    blue: u32,
-    green: u32,
-}
-
-type Day2 = Vec<Game>;
+use aoc23::{
+    cli::{exit_on_error, load, load_with, print_answers, DayArgs},
+    day2::{possible_games, Bag, Day2},
+};
 
 fn main() {
-    let input = read_input("inputs/day2.txt");
-
-    let p1_result = puzzle1(&input);
-    let p2_result = puzzle2(&input);
-
-    println!("Puzzle #1: {}", p1_result);
-    println!("Puzzle #2: {}", p2_result);
-}
-
-fn read_input(path: &str) -> Day2 {
-    let input = fs::read_to_string(path).unwrap();
-
-    input
-        .lines()
-        .map(|l| {
-            let mut parts = l.split(":");
-            let game_data = parts.next().unwrap();
-            let id: u32;
-
-            id = game_data
-                .split(" ")
-                .skip(1)
-                .next()
-                .unwrap()
-                .parse::<u32>()
-                .unwrap();
-
-            let rounds: Vec<Round> = parts
-                .next()
-                .unwrap()
-                .split(";")
-                .map(|s| parse_round(s))
-                .collect();
-
-            Game { id, rounds }
-        })
-        .collect()
-}
-
-fn parse_round(round_data: &str) -> Round {
-    let mut round = Round {
-        green: 0,
-        blue: 0,
-        red: 0,
-    };
-
-    for pick in round_data.trim().split(",") {
-        let mut pick_data = pick.trim().split(" ");
-        let total_cubes: u32 = pick_data.next().unwrap().parse::<u32>().unwrap();
-
-        match pick_data.next().unwrap() {
-            "blue" => round.blue += total_cubes,
-            "green" => round.green += total_cubes,
-            _ => round.red += total_cubes,
+    let args = DayArgs::from_env(
+        "inputs/day2.txt",
+        &["--report", "--format"],
+        &["--bag", "--bag-file"],
+    );
+    let input = exit_on_error(load::<Day2>(&args.input));
+
+    if args.flag("--format") {
+        for game in input.iter() {
+            println!("{}", game);
         }
-    }
 
-    round
-}
+        return;
+    }
 
-fn puzzle1(input: &Day2) -> u32 {
-    let max_red: u32 = 12;
-    let max_green: u32 = 13;
-    let max_blue: u32 = 14;
+    let mut bags: Vec<Bag> = args
+        .occurrences(&["--bag", "--bag-file"])
+        .map(|(option, value)| exit_on_error(load_bag(option, value)))
+        .collect();
 
-    input
-        .iter()
-        .filter(|game| {
-            let mut valid = true;
+    if args.flag("--report") {
+        if bags.is_empty() {
+            bags.push(Bag::default());
+        }
 
-            for Round { red, green, blue } in game.rounds.iter() {
-                if *red > max_red || *green > max_green || *blue > max_blue {
-                    valid = false;
-                    break;
-                }
-            }
+        for (number, bag) in bags.iter().enumerate() {
+            println!("Bag {}: {}", number + 1, bag);
+        }
 
-            valid
-        })
-        .map(|game| game.id)
-        .sum::<u32>()
-}
+        for game in input.iter() {
+            println!("{}", game.infer(&bags));
+        }
 
-fn puzzle2(input: &Day2) -> u32 {
-    input
-        .iter()
-        .map(|game| {
-            let mut min_red = 0;
-            let mut min_green = 0;
-            let mut min_blue = 0;
+        return;
+    }
 
-            for Round { red, green, blue } in game.rounds.iter() {
-                if *red > min_red {
-                    min_red = *red
-                }
-                if *green > min_green {
-                    min_green = *green
-                }
-                if *blue > min_blue {
-                    min_blue = *blue
-                }
-            }
+    if bags.is_empty() {
+        print_answers::<Day2>(&input);
+        return;
+    }
 
-            min_red * min_green * min_blue
-        })
-        .sum::<u32>()
+    for bag in bags.iter() {
+        println!(
+            "Possible games with {}: {}",
+            bag,
+            possible_games(&input, bag)
+        );
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn puzzle1_test() {
-        let test_input = read_input("inputs/day2_test.txt");
-
-        assert_eq!(puzzle1(&test_input), 8)
+fn load_bag(option: &str, value: &str) -> Result<Bag, String> {
+    if option == "--bag" {
+        return Bag::parse(value).map_err(|err| err.with_file("--bag").to_string());
     }
 
-    #[test]
-    fn puzzle2_test() {
-        let test_input = read_input("inputs/day2_test.txt");
-
-        assert_eq!(puzzle2(&test_input), 2286)
-    }
+    load_with(value, Bag::parse)
 }
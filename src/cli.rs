@@ -0,0 +1,161 @@
+use std::{env, io, process};
+
+use crate::{input_name, read_input, ParseError, Solution};
+
+/// Command line of a `dayX` binary: `--input <path|->` plus whichever flags
+/// and valued options that day understands.
+#[derive(Debug)]
+pub struct DayArgs {
+    pub input: String,
+    flags: Vec<String>,
+    options: Vec<(String, String)>,
+}
+
+impl DayArgs {
+    pub fn parse(
+        mut args: impl Iterator<Item = String>,
+        default_input: &str,
+        flags: &[&str],
+        options: &[&str],
+    ) -> Result<Self, String> {
+        let mut day_args = DayArgs {
+            input: default_input.to_string(),
+            flags: vec![],
+            options: vec![],
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => day_args.input = args.next().ok_or("missing value for --input")?,
+                _ if flags.contains(&arg.as_str()) => day_args.flags.push(arg),
+                _ if options.contains(&arg.as_str()) => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("missing value for {}", arg))?;
+
+                    day_args.options.push((arg, value));
+                }
+                _ => return Err(format!("unexpected argument '{}'", arg)),
+            }
+        }
+
+        Ok(day_args)
+    }
+
+    /// Parses the process arguments, exiting with a usage message on error.
+    pub fn from_env(default_input: &str, flags: &[&str], options: &[&str]) -> Self {
+        let mut args = env::args();
+        let program = args.next().unwrap_or_default();
+
+        DayArgs::parse(args, default_input, flags, options).unwrap_or_else(|message| {
+            let mut usage = format!("usage: {} [--input <path|->]", program);
+
+            for flag in flags {
+                usage += &format!(" [{}]", flag);
+            }
+
+            for option in options {
+                usage += &format!(" [{} <value>]", option);
+            }
+
+            eprintln!("error: {}", message);
+            eprintln!("{}", usage);
+            process::exit(1);
+        })
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+
+    /// Value of the last occurrence of `name`.
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(option, _)| option == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Every occurrence of any of `names`, in command line order.
+    pub fn occurrences<'a>(
+        &'a self,
+        names: &'a [&str],
+    ) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.options
+            .iter()
+            .filter(|(option, _)| names.contains(&option.as_str()))
+            .map(|(option, value)| (option.as_str(), value.as_str()))
+    }
+}
+
+pub fn load<S: Solution>(path: &str) -> Result<S::Input, String> {
+    load_with(path, S::parse)
+}
+
+/// Reads `path` and hands it to `parse`, naming the file in any error.
+pub fn load_with<T>(
+    path: &str,
+    parse: impl FnOnce(&str) -> Result<T, ParseError>,
+) -> Result<T, String> {
+    parse(&read(path)?).map_err(|err| err.with_file(input_name(path)).to_string())
+}
+
+pub fn read(path: &str) -> Result<String, String> {
+    read_input(path).map_err(|err| read_error(path, &err))
+}
+
+pub fn read_error(path: &str, err: &io::Error) -> String {
+    format!("could not read '{}': {}", input_name(path), err)
+}
+
+pub fn print_answers<S: Solution>(input: &S::Input) {
+    let p1_result = S::part1(input);
+    println!("Puzzle #1: {}", p1_result);
+
+    let p2_result = S::part2(input);
+    println!("Puzzle #2: {}", p2_result);
+}
+
+pub fn exit_on_error<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|message| {
+        eprintln!("error: {}", message);
+        process::exit(1);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Result<DayArgs, String> {
+        DayArgs::parse(
+            line.split_whitespace().map(String::from),
+            "inputs/day5.txt",
+            &["--flatten"],
+            &["--bag"],
+        )
+    }
+
+    #[test]
+    fn day_args_test() {
+        let parsed = args("--flatten --bag red=1 --input - --bag red=2").unwrap();
+
+        assert_eq!(parsed.input, "-");
+        assert!(parsed.flag("--flatten"));
+        assert_eq!(parsed.option("--bag"), Some("red=2"));
+        assert_eq!(
+            parsed.occurrences(&["--bag"]).collect::<Vec<_>>(),
+            vec![("--bag", "red=1"), ("--bag", "red=2")]
+        );
+
+        let parsed = args("").unwrap();
+
+        assert_eq!(parsed.input, "inputs/day5.txt");
+        assert!(!parsed.flag("--flatten"));
+        assert_eq!(parsed.option("--bag"), None);
+
+        assert!(args("--strict").is_err());
+        assert!(args("--bag").is_err());
+    }
+}
@@ -0,0 +1,469 @@
+use std::{
+    io::{self, BufRead},
+    str::FromStr,
+};
+
+use aho_corasick::{AhoCorasick, MatchKind};
+
+use crate::{parse_number, ParseError, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<String>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        puzzle1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output {
+        puzzle2(input)
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(input.lines().map(|l| l.to_owned()).collect())
+}
+
+/// Spelled-out numbers recognised in calibration lines on top of the plain
+/// digits, as `word → value`.
+///
+/// A word counts as if its value had been written in numerals, so with
+/// `eleven = 11` the line `eleven4` calibrates to `14` and `4eleven` to `41`.
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    pub fn new() -> Self {
+        Vocabulary::default()
+    }
+
+    /// "one" to "nine", as in the puzzle.
+    pub fn english() -> Self {
+        [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ]
+        .iter()
+        .zip(1..)
+        .fold(Vocabulary::new(), |vocabulary, (word, value)| {
+            vocabulary.word(word, value)
+        })
+    }
+
+    /// Adds `word`, which has to be non-empty and free of digits.
+    pub fn word(mut self, word: &str, value: u32) -> Self {
+        assert!(is_word(word), "'{}' is not a word without digits", word);
+
+        self.words.retain(|(w, _)| w != word);
+        self.words.push((word.to_string(), value));
+        self
+    }
+
+    /// Reads one `word = value` pair per line, `#` starting a comment.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut vocabulary = Vocabulary::new();
+
+        for (line_index, raw_line) in input.lines().enumerate() {
+            let line = raw_line.split('#').next().unwrap().trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let (word, value) = line
+                .split_once('=')
+                .map(|(word, value)| (word.trim(), value.trim()))
+                .ok_or_else(|| {
+                    ParseError::new(line_index, raw_line, line, "expected '<word> = <value>'")
+                })?;
+
+            if !is_word(word) {
+                return Err(ParseError::new(
+                    line_index,
+                    raw_line,
+                    word,
+                    "expected a word without digits",
+                ));
+            }
+
+            vocabulary = vocabulary.word(word, parse_number(line_index, raw_line, value)?);
+        }
+
+        Ok(vocabulary)
+    }
+}
+
+fn is_word(word: &str) -> bool {
+    !word.is_empty() && !word.chars().any(|c| c.is_ascii_digit())
+}
+
+/// Finds the first and last digit tokens of a line, numerals or vocabulary
+/// words, with an Aho-Corasick automaton over every token.
+///
+/// The first token is the leftmost one and the last token the rightmost one,
+/// the longest winning ties. Tokens may overlap: `oneight` reads 1 then 8.
+#[derive(Debug)]
+pub struct Scanner {
+    forward: AhoCorasick,
+    backward: AhoCorasick,
+    values: Vec<u32>,
+    longest: usize,
+}
+
+impl Scanner {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let tokens: Vec<(String, u32)> = (0..10)
+            .map(|digit| (digit.to_string(), digit))
+            .chain(vocabulary.words.iter().cloned())
+            .collect();
+
+        let build = |patterns: Vec<Vec<u8>>| {
+            AhoCorasick::builder()
+                .match_kind(MatchKind::LeftmostLongest)
+                .build(patterns)
+                .unwrap()
+        };
+
+        Scanner {
+            forward: build(tokens.iter().map(|(t, _)| t.bytes().collect()).collect()),
+            backward: build(
+                tokens
+                    .iter()
+                    .map(|(t, _)| t.bytes().rev().collect())
+                    .collect(),
+            ),
+            values: tokens.iter().map(|(_, value)| *value).collect(),
+            longest: tokens.iter().map(|(t, _)| t.len()).max().unwrap(),
+        }
+    }
+
+    pub fn first(&self, line: &str) -> Option<u32> {
+        self.forward
+            .find(line)
+            .map(|m| self.values[m.pattern().as_usize()])
+    }
+
+    /// Searches the reversed line from its end in growing windows, so only
+    /// the tail up to the last token is ever looked at.
+    pub fn last(&self, line: &str) -> Option<u32> {
+        let bytes = line.as_bytes();
+        let mut window = 4 * self.longest;
+
+        loop {
+            let reversed: Vec<u8> = bytes.iter().rev().take(window).copied().collect();
+            let complete = reversed.len() == bytes.len();
+
+            match self.backward.find(&reversed[..]) {
+                // A longer token starting at the same place could still be
+                // cut off by the window.
+                Some(m) if complete || m.start() + self.longest <= reversed.len() => {
+                    return Some(self.values[m.pattern().as_usize()])
+                }
+                _ if complete => return None,
+                _ => window *= 2,
+            }
+        }
+    }
+
+    /// First and last digit of `line` read as a two digit number, if it has
+    /// any digit at all.
+    pub fn calibration_value(&self, line: &str) -> Option<u32> {
+        let first_digit = leading_digit(self.first(line)?);
+        let last_digit = self.last(line)? % 10;
+
+        Some((first_digit * 10) + last_digit)
+    }
+}
+
+/// What to do with a line that holds no digit at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoDigits {
+    /// Leave the line out of the calibration.
+    #[default]
+    Skip,
+    /// Count the line as `00`.
+    Zero,
+    /// Stop with an error pointing at the line.
+    Error,
+}
+
+impl FromStr for NoDigits {
+    type Err = String;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy {
+            "skip" => Ok(NoDigits::Skip),
+            "zero" => Ok(NoDigits::Zero),
+            "error" => Ok(NoDigits::Error),
+            _ => Err(format!(
+                "unknown policy '{}', expected skip, zero or error",
+                policy
+            )),
+        }
+    }
+}
+
+/// Running calibration total, fed one line at a time.
+#[derive(Debug)]
+pub struct Calibrator {
+    scanner: Scanner,
+    policy: NoDigits,
+    lines: usize,
+    calibrated: usize,
+    total: u64,
+    missing: Vec<usize>,
+}
+
+impl Calibrator {
+    pub fn new(vocabulary: &Vocabulary, policy: NoDigits) -> Self {
+        Calibrator {
+            scanner: Scanner::new(vocabulary),
+            policy,
+            lines: 0,
+            calibrated: 0,
+            total: 0,
+            missing: vec![],
+        }
+    }
+
+    pub fn add(&mut self, line: &str) -> Result<(), ParseError> {
+        let line_index = self.lines;
+        self.lines += 1;
+
+        let value = match (self.scanner.calibration_value(line), self.policy) {
+            (Some(value), _) => value,
+            (None, NoDigits::Error) => {
+                return Err(ParseError::new(line_index, line, line, "line has no digit"))
+            }
+            (None, policy) => {
+                self.missing.push(self.lines);
+
+                if policy == NoDigits::Skip {
+                    return Ok(());
+                }
+
+                0
+            }
+        };
+
+        self.calibrated += 1;
+        self.total += u64::from(value);
+        Ok(())
+    }
+
+    /// Lines read so far, with or without a digit.
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+
+    /// Lines that made it into the total.
+    pub fn calibrated(&self) -> usize {
+        self.calibrated
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// 1-based numbers of the lines without a digit.
+    pub fn missing(&self) -> &[usize] {
+        &self.missing
+    }
+}
+
+fn puzzle1(input: &[String]) -> u64 {
+    calibrate(input, &Vocabulary::new(), NoDigits::Skip)
+        .unwrap()
+        .total()
+}
+
+fn puzzle2(input: &[String]) -> u64 {
+    calibrate(input, &Vocabulary::english(), NoDigits::Skip)
+        .unwrap()
+        .total()
+}
+
+/// Sum over every line of its first and last digit read as a two digit
+/// number, counting both numerals and the words of `vocabulary`.
+pub fn calibrate(
+    input: &[String],
+    vocabulary: &Vocabulary,
+    policy: NoDigits,
+) -> Result<Calibrator, ParseError> {
+    let mut calibrator = Calibrator::new(vocabulary, policy);
+
+    for line in input {
+        calibrator.add(line)?;
+    }
+
+    Ok(calibrator)
+}
+
+/// Feeds `reader` to every calibrator line by line, reusing a single line
+/// buffer so memory stays flat however large the input. `report` is called
+/// after each line with the running totals.
+///
+/// A line rejected by a calibrator's policy comes back as an `InvalidData`
+/// error wrapping its `ParseError`.
+pub fn calibrate_stream(
+    mut reader: impl BufRead,
+    calibrators: &mut [Calibrator],
+    mut report: impl FnMut(&[Calibrator]),
+) -> io::Result<()> {
+    let mut buffer = String::new();
+
+    while reader.read_line(&mut buffer)? > 0 {
+        let line = buffer.trim_end_matches('\n').trim_end_matches('\r');
+
+        for calibrator in calibrators.iter_mut() {
+            calibrator
+                .add(line)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        }
+
+        report(calibrators);
+        buffer.clear();
+    }
+
+    Ok(())
+}
+
+fn leading_digit(mut value: u32) -> u32 {
+    while value >= 10 {
+        value /= 10;
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    #[test]
+    fn puzzle1_test() {
+        let test_input = parse(&read_input("inputs/day1_1_test.txt").unwrap()).unwrap();
+
+        assert_eq!(puzzle1(&test_input), 142)
+    }
+
+    #[test]
+    fn puzzle2_test() {
+        let test_input = parse(&read_input("inputs/day1_2_test.txt").unwrap()).unwrap();
+        assert_eq!(puzzle2(&test_input), 281)
+    }
+
+    #[test]
+    fn scanner_test() {
+        let scanner = Scanner::new(&Vocabulary::english());
+
+        assert_eq!(scanner.first("xoneightx"), Some(1));
+        assert_eq!(scanner.last("xoneightx"), Some(8));
+        assert_eq!(scanner.first("twone"), Some(2));
+        assert_eq!(scanner.last("twone"), Some(1));
+        assert_eq!(scanner.last("abc"), None);
+
+        let long_line = format!("seven{}", "x".repeat(1000));
+
+        assert_eq!(scanner.last(&long_line), Some(7));
+        assert_eq!(scanner.last(&format!("{}nine", long_line)), Some(9));
+
+        let scanner = Scanner::new(&Vocabulary::english().word("seventeen", 17));
+
+        assert_eq!(scanner.first("seventeen"), Some(17));
+        assert_eq!(
+            scanner.last(&format!("seventeen{}", "x".repeat(28))),
+            Some(17)
+        );
+    }
+
+    #[test]
+    fn stream_test() {
+        let input = read_input("inputs/day1_2_test.txt").unwrap();
+        let mut calibrators = [
+            Calibrator::new(&Vocabulary::new(), NoDigits::Skip),
+            Calibrator::new(&Vocabulary::english(), NoDigits::Skip),
+        ];
+        let mut running = vec![];
+
+        calibrate_stream(input.as_bytes(), &mut calibrators[1..], |c| {
+            running.push(c[0].total())
+        })
+        .unwrap();
+
+        assert_eq!(running, vec![29, 112, 125, 149, 191, 205, 281]);
+        assert_eq!(calibrators[1].lines(), 7);
+
+        calibrate_stream("a1b\r\nc2d".as_bytes(), &mut calibrators[..1], |_| {}).unwrap();
+
+        assert_eq!(calibrators[0].total(), 11 + 22);
+
+        let mut strict = [Calibrator::new(&Vocabulary::new(), NoDigits::Error)];
+        let error = calibrate_stream("1\nx\n".as_bytes(), &mut strict, |_| {}).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn no_digits_test() {
+        let lines = parse("a1\n\nnone\n2b\n").unwrap();
+
+        let skipped = calibrate(&lines, &Vocabulary::new(), NoDigits::Skip).unwrap();
+
+        assert_eq!((skipped.total(), skipped.calibrated()), (33, 2));
+        assert_eq!(skipped.missing(), &[2, 3]);
+
+        let zeroed = calibrate(&lines, &Vocabulary::new(), NoDigits::Zero).unwrap();
+
+        assert_eq!((zeroed.total(), zeroed.calibrated()), (33, 4));
+        assert_eq!(zeroed.missing(), &[2, 3]);
+
+        let worded = calibrate(&lines, &Vocabulary::english(), NoDigits::Zero).unwrap();
+
+        assert_eq!(worded.missing(), &[2]);
+
+        let error = calibrate(&lines[2..], &Vocabulary::new(), NoDigits::Error).unwrap_err();
+
+        assert_eq!((error.line, error.snippet.as_str()), (1, "none"));
+        assert_eq!("zero".parse(), Ok(NoDigits::Zero));
+        assert!("drop".parse::<NoDigits>().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "'' is not a word without digits")]
+    fn empty_word_test() {
+        let _ = Vocabulary::english().word("", 5);
+    }
+
+    #[test]
+    fn vocabulary_test() {
+        let vocabulary = Vocabulary::parse("# German\nnull = 0\neins = 1\nzwölf = 12\n").unwrap();
+        let lines = parse("xnullx3\neinszwölf\n7seventeen\n").unwrap();
+
+        assert_eq!(
+            calibrate(&lines, &vocabulary, NoDigits::Error)
+                .unwrap()
+                .total(),
+            3 + 12 + 77
+        );
+
+        let vocabulary = Vocabulary::english().word("seventeen", 17).word("ten", 10);
+
+        let calibrate = |lines: &[String]| calibrate(lines, &vocabulary, NoDigits::Error).unwrap();
+
+        assert_eq!(calibrate(&lines[2..]).total(), 77);
+        assert_eq!(calibrate(&parse("tenxone").unwrap()).total(), 11);
+
+        let error = Vocabulary::parse("one = 1\n2 = two\n").unwrap_err();
+
+        assert_eq!((error.line, error.snippet.as_str()), (2, "2"));
+    }
+}
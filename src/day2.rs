@@ -0,0 +1,587 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+use crate::{parse_number, ParseError, Solution};
+
+/// Cube colors of the puzzle itself.
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+/// One line of the record, `Game <id>: <n> <color>, ...; ...`.
+///
+/// Displays back in canonical form: single spaces, colors in alphabetical
+/// order within a round.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Game {
+    id: u32,
+    rounds: Vec<Round>,
+}
+
+impl Game {
+    /// Fewest cubes of each color the game could have been played with.
+    pub fn minimal_bag(&self) -> Bag {
+        let mut bag = BTreeMap::new();
+
+        for round in self.rounds.iter() {
+            for (color, count) in round.0.iter() {
+                let most = bag.entry(color.clone()).or_insert(0);
+                *most = (*most).max(*count);
+            }
+        }
+
+        Bag(bag)
+    }
+
+    /// Checks the game against every one of `bags`.
+    pub fn infer(&self, bags: &[Bag]) -> Inference {
+        let minimal = self.minimal_bag();
+
+        let limiting = minimal
+            .0
+            .iter()
+            .map(|(color, most)| {
+                let round = self
+                    .rounds
+                    .iter()
+                    .position(|round| round.count(color) == *most)
+                    .unwrap();
+
+                (color.clone(), round + 1)
+            })
+            .collect();
+
+        Inference {
+            id: self.id,
+            feasible: bags.iter().map(|bag| bag.contains(&minimal)).collect(),
+            minimal,
+            limiting,
+        }
+    }
+}
+
+/// What a game tells about the bag it was played with.
+#[derive(Debug)]
+pub struct Inference {
+    pub id: u32,
+    pub minimal: Bag,
+    /// First round showing the most cubes of each color, 1-based.
+    pub limiting: BTreeMap<String, usize>,
+    /// Whether the game fits each of the candidate bags, in order.
+    pub feasible: Vec<bool>,
+}
+
+impl fmt::Display for Inference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limiting: Vec<String> = self
+            .limiting
+            .iter()
+            .map(|(color, round)| format!("{} in round {}", color, round))
+            .collect();
+
+        let feasible: Vec<String> = self
+            .feasible
+            .iter()
+            .enumerate()
+            .filter(|(_, feasible)| **feasible)
+            .map(|(bag, _)| (bag + 1).to_string())
+            .collect();
+
+        write!(
+            f,
+            "Game {}: needs {}; limited by {}; fits ",
+            self.id,
+            self.minimal,
+            limiting.join(", ")
+        )?;
+
+        match feasible.len() {
+            0 => write!(f, "no bag"),
+            1 => write!(f, "bag {}", feasible[0]),
+            _ => write!(f, "bags {}", feasible.join(", ")),
+        }
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rounds: Vec<String> = self.rounds.iter().map(Round::to_string).collect();
+
+        write!(f, "Game {}: {}", self.id, rounds.join("; "))
+    }
+}
+
+/// Cubes shown in one round, by color.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Round(BTreeMap<String, u32>);
+
+impl Round {
+    fn count(&self, color: &str) -> u32 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_cubes(&self.0))
+    }
+}
+
+/// `<n> <color>, <n> <color>, ...`
+fn format_cubes(cubes: &BTreeMap<String, u32>) -> String {
+    let cubes: Vec<String> = cubes
+        .iter()
+        .map(|(color, count)| format!("{} {}", count, color))
+        .collect();
+
+    cubes.join(", ")
+}
+
+/// How many cubes of each color the bag holds. A color the bag does not list
+/// is one it has no cubes of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag(BTreeMap<String, u32>);
+
+impl Default for Bag {
+    /// 12 red, 13 green and 14 blue cubes, as in the puzzle.
+    fn default() -> Self {
+        Bag([("red", 12), ("green", 13), ("blue", 14)]
+            .iter()
+            .map(|(color, count)| (color.to_string(), *count))
+            .collect())
+    }
+}
+
+impl Bag {
+    /// Reads `<count> <color>` pairs separated by commas or newlines, such as
+    /// `12 red, 13 green, 14 blue`, `#` starting a comment.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut bag = BTreeMap::new();
+        let mut colors = vec![];
+
+        for (line_index, raw_line) in input.lines().enumerate() {
+            let mut tokens = Tokens::new(line_index, raw_line, raw_line.split('#').next().unwrap());
+
+            while tokens.peek().is_some() {
+                let (count, color) = tokens.pick()?;
+
+                if bag.insert(color.to_string(), count).is_some() {
+                    return Err(tokens.error(color, "color listed twice in the bag"));
+                }
+
+                if tokens.peek().is_some() {
+                    tokens.expect(",")?;
+                }
+            }
+
+            colors.extend(tokens.colors());
+        }
+
+        check_plurals(&colors)?;
+        Ok(Bag(bag))
+    }
+
+    fn count(&self, color: &str) -> u32 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    /// Whether the bag holds enough cubes of every color shown in `round`.
+    fn allows(&self, round: &Round) -> bool {
+        round
+            .0
+            .iter()
+            .all(|(color, count)| *count <= self.count(color))
+    }
+
+    /// Whether the bag holds at least as many cubes of every color as `other`.
+    pub fn contains(&self, other: &Bag) -> bool {
+        other
+            .0
+            .iter()
+            .all(|(color, count)| *count <= self.count(color))
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_cubes(&self.0))
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Game>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        puzzle1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output {
+        puzzle2(input)
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    let mut games = vec![];
+    let mut colors = vec![];
+
+    for (line_index, line) in input.lines().enumerate() {
+        let mut tokens = Tokens::new(line_index, line, line);
+
+        games.push(parse_game(&mut tokens)?);
+        colors.extend(tokens.colors());
+    }
+
+    check_plurals(&colors)?;
+    Ok(games)
+}
+
+/// Rejects a color spelled as another known color plus an `s`, like `blues`
+/// next to `blue`. Known colors are the puzzle's and those of `colors`,
+/// given as `(line_index, line, color)`.
+fn check_plurals(colors: &[(usize, &str, &str)]) -> Result<(), ParseError> {
+    let known: HashSet<&str> = colors
+        .iter()
+        .map(|(_, _, color)| *color)
+        .chain(COLORS)
+        .collect();
+
+    for (line_index, line, color) in colors {
+        if color
+            .strip_suffix('s')
+            .is_some_and(|stem| known.contains(stem))
+        {
+            return Err(ParseError::new(
+                *line_index,
+                line,
+                color,
+                "expected a color in the singular",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// `Game <id>: <round>; <round>; ...`
+fn parse_game(tokens: &mut Tokens) -> Result<Game, ParseError> {
+    tokens.expect("Game")?;
+
+    let id = tokens.number()?;
+    let mut rounds = vec![];
+
+    tokens.expect(":")?;
+
+    loop {
+        rounds.push(parse_round(tokens)?);
+
+        match tokens.peek() {
+            None => return Ok(Game { id, rounds }),
+            Some(";") => tokens.expect(";")?,
+            Some(token) => return Err(tokens.error(token, "expected ',' or ';'")),
+        }
+    }
+}
+
+/// `<n> <color>, <n> <color>, ...`
+fn parse_round(tokens: &mut Tokens) -> Result<Round, ParseError> {
+    let mut round = Round::default();
+
+    loop {
+        let (count, color) = tokens.pick()?;
+
+        if round.0.insert(color.to_string(), count).is_some() {
+            return Err(tokens.error(color, "color listed twice in the round"));
+        }
+
+        if tokens.peek() != Some(",") {
+            return Ok(round);
+        }
+
+        tokens.expect(",")?;
+    }
+}
+
+/// Words, numbers and the `:`, `,` and `;` separators of a line, whitespace
+/// dropped.
+struct Tokens<'a> {
+    line_index: usize,
+    line: &'a str,
+    tokens: Vec<&'a str>,
+    end: &'a str,
+    position: usize,
+    colors: Vec<&'a str>,
+}
+
+impl<'a> Tokens<'a> {
+    /// `text` has to be a slice of `line`, so errors point at the right column.
+    fn new(line_index: usize, line: &'a str, text: &'a str) -> Self {
+        let mut tokens = vec![];
+        let mut start = None;
+
+        for (index, c) in text.char_indices() {
+            if c.is_whitespace() || ":,;".contains(c) {
+                if let Some(start) = start.take() {
+                    tokens.push(&text[start..index]);
+                }
+
+                if !c.is_whitespace() {
+                    tokens.push(&text[index..index + 1]);
+                }
+            } else if start.is_none() {
+                start = Some(index);
+            }
+        }
+
+        if let Some(start) = start {
+            tokens.push(&text[start..]);
+        }
+
+        Tokens {
+            line_index,
+            line,
+            tokens,
+            end: &text[text.len()..],
+            position: 0,
+            colors: vec![],
+        }
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        let token = self.peek().ok_or_else(|| self.error(self.end, expected))?;
+
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), ParseError> {
+        let expected = format!("expected '{}'", symbol);
+        let token = self.next(&expected)?;
+
+        if token != symbol {
+            return Err(self.error(token, expected));
+        }
+
+        Ok(())
+    }
+
+    /// Plain digits only, no sign.
+    fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let token = self.next("expected a number")?;
+
+        if !token.chars().all(|c| c.is_ascii_digit()) {
+            return Err(self.error(token, "expected a number"));
+        }
+
+        parse_number(self.line_index, self.line, token)
+    }
+
+    /// A lowercase word: `blue`, not `Blue`.
+    fn color(&mut self) -> Result<&'a str, ParseError> {
+        let token = self.next("expected a color")?;
+
+        if !token.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(self.error(token, "expected a color"));
+        }
+
+        self.colors.push(token);
+        Ok(token)
+    }
+
+    /// Every color read so far, as `(line_index, line, color)`.
+    fn colors(&self) -> impl Iterator<Item = (usize, &'a str, &'a str)> + '_ {
+        self.colors
+            .iter()
+            .map(|color| (self.line_index, self.line, *color))
+    }
+
+    /// `<n> <color>`
+    fn pick(&mut self) -> Result<(u32, &'a str), ParseError> {
+        Ok((self.number()?, self.color()?))
+    }
+
+    fn error(&self, token: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.line_index, self.line, token, message)
+    }
+}
+
+fn puzzle1(input: &[Game]) -> u64 {
+    possible_games(input, &Bag::default())
+}
+
+/// Sum of the ids of the games that could have been played with `bag`.
+pub fn possible_games(input: &[Game], bag: &Bag) -> u64 {
+    input
+        .iter()
+        .filter(|game| game.rounds.iter().all(|round| bag.allows(round)))
+        .map(|game| u64::from(game.id))
+        .sum::<u64>()
+}
+
+/// Sum over every game of the product of the fewest red, green and blue
+/// cubes it could have been played with.
+fn puzzle2(input: &[Game]) -> u64 {
+    input
+        .iter()
+        .map(|game| {
+            let minimal = game.minimal_bag();
+
+            COLORS
+                .iter()
+                .map(|color| u64::from(minimal.count(color)))
+                .product::<u64>()
+        })
+        .sum::<u64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    #[test]
+    fn puzzle1_test() {
+        let test_input = parse(&read_input("inputs/day2_test.txt").unwrap()).unwrap();
+
+        assert_eq!(puzzle1(&test_input), 8)
+    }
+
+    #[test]
+    fn puzzle2_test() {
+        let test_input = parse(&read_input("inputs/day2_test.txt").unwrap()).unwrap();
+
+        assert_eq!(puzzle2(&test_input), 2286);
+
+        let games = parse("Game 1: 70000 red, 70000 green, 1 blue").unwrap();
+
+        assert_eq!(puzzle2(&games), 4_900_000_000);
+    }
+
+    #[test]
+    fn infer_test() {
+        let test_input = parse(&read_input("inputs/day2_test.txt").unwrap()).unwrap();
+        let bags = [
+            Bag::default(),
+            Bag::parse("20 red, 13 green, 6 blue").unwrap(),
+        ];
+
+        let inference = test_input[2].infer(&bags);
+
+        assert_eq!(inference.minimal.to_string(), "6 blue, 13 green, 20 red");
+        assert_eq!(inference.feasible, vec![false, true]);
+        assert_eq!(
+            inference.to_string(),
+            "Game 3: needs 6 blue, 13 green, 20 red; \
+             limited by blue in round 1, green in round 2, red in round 1; fits bag 2"
+        );
+
+        let feasible: Vec<bool> = test_input
+            .iter()
+            .map(|game| game.infer(&bags).feasible[0])
+            .collect();
+
+        assert_eq!(feasible, vec![true, true, false, false, true]);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let error = parse("Game 1: 3 blue\nGame 2: 1 red, blue").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 16));
+        assert_eq!(error.snippet, "blue");
+
+        let error = parse("Game 1: 3 blue, 2 Red").unwrap_err();
+
+        assert_eq!((error.column, error.snippet.as_str()), (19, "Red"));
+
+        let errors = [
+            ("Game : 3 blue", 6, ":", "expected a number"),
+            ("Game +1: 3 blue", 6, "+1", "expected a number"),
+            ("Game 1: +3 blue", 9, "+3", "expected a number"),
+            (
+                "Game 1: 3 blue; 1 blues",
+                19,
+                "blues",
+                "expected a color in the singular",
+            ),
+            ("Game 1: 3 blue 4 red", 16, "4", "expected ',' or ';'"),
+            (
+                "Game 1: 3 blue, 4 red, 1 blue",
+                26,
+                "blue",
+                "color listed twice in the round",
+            ),
+            ("Game 1: 3 blue;", 16, "", "expected a number"),
+            ("Game1: 3 blue", 1, "Game1", "expected 'Game'"),
+        ];
+
+        for (line, column, snippet, message) in errors {
+            let error = parse(line).unwrap_err();
+
+            assert_eq!(
+                (error.column, error.snippet.as_str(), error.message.as_str()),
+                (column, snippet, message),
+                "{}",
+                line
+            );
+        }
+
+        assert!(parse("Game 1: 3 iris, 2 moss").is_ok());
+
+        let error = parse("Game 1: 1 tans\nGame 2: 2 tan").unwrap_err();
+
+        assert_eq!((error.line, error.snippet.as_str()), (1, "tans"));
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let games = parse("Game  7 :4 red,3 blue ;  1 green").unwrap();
+
+        assert_eq!(games[0].to_string(), "Game 7: 3 blue, 4 red; 1 green");
+
+        let input = read_input("inputs/day2.txt").unwrap();
+        let games = parse(&input).unwrap();
+        let text: Vec<String> = games.iter().map(Game::to_string).collect();
+
+        assert_eq!(parse(&text.join("\n")).unwrap(), games);
+    }
+
+    #[test]
+    fn bag_test() {
+        let games = parse(&read_input("inputs/day2_test.txt").unwrap()).unwrap();
+
+        assert_eq!(
+            Bag::parse("12 red, 13 green, 14 blue").unwrap(),
+            Bag::default()
+        );
+
+        let bag = Bag::parse("# bigger bag\n20 red, 13 green\n15 blue\n").unwrap();
+
+        assert_eq!(possible_games(&games, &bag), 1 + 2 + 3 + 4 + 5);
+
+        let games = parse("Game 1: 1 red\nGame 2: 1 red, 1 purple").unwrap();
+
+        assert_eq!(possible_games(&games, &Bag::default()), 1);
+        assert!(Bag::parse("3 iris, 1 moss").is_ok());
+        assert!(Bag::parse("3 purple\n1 purples").is_err());
+        assert_eq!(
+            possible_games(&games, &Bag::parse("1 red, 1 purple").unwrap()),
+            3
+        );
+
+        let error = Bag::parse("1 red\n2 blue, 3 red").unwrap_err();
+
+        assert_eq!((error.line, error.snippet.as_str()), (2, "red"));
+    }
+}
@@ -0,0 +1,554 @@
+use std::{fmt, iter, str::FromStr};
+
+use crate::{
+    grid::{Grid, Position},
+    interval::Interval,
+    parse_number, ParseError, Solution,
+};
+
+/// Where a number sits in the schematic: its row and the columns of its
+/// digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub row: usize,
+    pub columns: Interval,
+}
+
+impl Span {
+    /// Positions touching the span, diagonals included, that lie on `grid`.
+    fn surroundings<T>(self, grid: &Grid<T>) -> impl Iterator<Item = Position> + '_ {
+        let columns = self.columns.start.saturating_sub(1)..=self.columns.end;
+
+        (self.row.saturating_sub(1)..=self.row + 1)
+            .flat_map(move |row| columns.clone().map(move |column| (row, column)))
+            .filter(move |(row, column)| *row != self.row || !self.columns.contains(*column))
+            .filter(|position| grid.contains(*position))
+    }
+}
+
+/// What occupies a cell of the schematic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Symbol(char),
+    /// A digit of the number at this index of `Schematic::numbers`.
+    Number(usize),
+}
+
+/// The engine schematic along with every number found in it, each cell
+/// pointing at the symbol or number occupying it.
+#[derive(Debug)]
+pub struct Schematic {
+    cells: Grid<Cell>,
+    numbers: Vec<(u32, Span)>,
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Schematic;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        puzzle1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output {
+        puzzle2(input)
+    }
+}
+
+fn parse(input: &str) -> Result<Schematic, ParseError> {
+    let grid = Grid::parse(input, Some)?;
+    let mut numbers = vec![];
+
+    for (line_index, line) in input.lines().enumerate() {
+        numbers.extend(parse_row(line_index, line)?);
+    }
+
+    let mut cells = grid.map(|c| match c {
+        c if is_symbol(*c) => Cell::Symbol(*c),
+        _ => Cell::Empty,
+    });
+
+    for (index, (_, span)) in numbers.iter().enumerate() {
+        for column in span.columns.start..span.columns.end {
+            cells[(span.row, column)] = Cell::Number(index);
+        }
+    }
+
+    Ok(Schematic { cells, numbers })
+}
+
+/// Numbers of one row with their spans. A `.` past the end of the line closes
+/// a number running up to the edge like any other.
+fn parse_row(line_index: usize, line: &str) -> Result<Vec<(u32, Span)>, ParseError> {
+    let mut numbers = vec![];
+    let mut start: Option<(usize, usize)> = None;
+
+    let cells = line.char_indices().chain(iter::once((line.len(), '.')));
+
+    for (column, (offset, c)) in cells.enumerate() {
+        match (start, c.is_ascii_digit()) {
+            (None, true) => start = Some((column, offset)),
+            (Some((start_column, start_offset)), false) => {
+                let value = parse_number(line_index, line, &line[start_offset..offset])?;
+                let span = Span {
+                    row: line_index,
+                    columns: Interval::new(start_column, column),
+                };
+
+                numbers.push((value, span));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+
+    Ok(numbers)
+}
+
+fn is_symbol(c: char) -> bool {
+    !c.is_ascii_digit() && c != '.'
+}
+
+impl Schematic {
+    /// Every `query.symbol` whose count of adjacent numbers fits
+    /// `query.arity`, along with those numbers.
+    pub fn matches(&self, query: &Query) -> Vec<(Position, Vec<u32>)> {
+        self.cells
+            .iter()
+            .filter(|(_, cell)| **cell == Cell::Symbol(query.symbol))
+            .map(|(position, _)| (position, self.adjacent_numbers(position)))
+            .filter(|(_, numbers)| query.arity.accepts(numbers.len()))
+            .collect()
+    }
+
+    /// Aggregate of each match, summed over all of them.
+    pub fn answer(&self, query: &Query) -> u64 {
+        self.matches(query)
+            .iter()
+            .map(|(_, numbers)| query.aggregate.apply(numbers))
+            .sum()
+    }
+
+    /// Renders the schematic with part numbers highlighted, other numbers
+    /// dimmed, and the puzzle's gears marked along with their two numbers.
+    pub fn render(&self, style: Style) -> String {
+        let gears: Vec<Position> = self
+            .matches(&Query::gears())
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect();
+
+        let gear_numbers: Vec<usize> = gears
+            .iter()
+            .flat_map(|gear| self.adjacent_indices(*gear))
+            .collect();
+
+        let mut rendered = String::new();
+
+        for row in 0..self.cells.height() {
+            let mut column = 0;
+
+            while column < self.cells.width() {
+                match self.cells[(row, column)] {
+                    Cell::Number(index) => {
+                        let (value, span) = self.numbers[index];
+                        let mark = if gear_numbers.contains(&index) {
+                            Mark::Gear
+                        } else if self.is_part_number(&span) {
+                            Mark::Part
+                        } else {
+                            Mark::Isolated
+                        };
+
+                        rendered += &mark.apply(style, &value.to_string());
+                        column = span.columns.end;
+                        continue;
+                    }
+                    Cell::Symbol(symbol) if gears.contains(&(row, column)) => {
+                        rendered += &Mark::Gear.apply(style, &symbol.to_string());
+                    }
+                    Cell::Symbol(symbol) => rendered.push(symbol),
+                    Cell::Empty => rendered.push('.'),
+                }
+
+                column += 1;
+            }
+
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+
+    fn is_part_number(&self, span: &Span) -> bool {
+        span.surroundings(&self.cells)
+            .any(|position| matches!(self.cells[position], Cell::Symbol(_)))
+    }
+
+    fn adjacent_indices(&self, position: Position) -> Vec<usize> {
+        let mut indices: Vec<usize> = self
+            .cells
+            .neighbors8(position)
+            .filter_map(|neighbor| match self.cells[neighbor] {
+                Cell::Number(index) => Some(index),
+                _ => None,
+            })
+            .collect();
+
+        indices.sort();
+        indices.dedup();
+        indices
+    }
+
+    fn adjacent_numbers(&self, position: Position) -> Vec<u32> {
+        self.adjacent_indices(position)
+            .iter()
+            .map(|index| self.numbers[*index].0)
+            .collect()
+    }
+}
+
+/// How `Schematic::render` marks things up: ANSI colors for terminals,
+/// brackets for anything else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Ansi,
+    Plain,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Part,
+    Isolated,
+    Gear,
+}
+
+impl Mark {
+    fn apply(self, style: Style, text: &str) -> String {
+        match (style, self) {
+            (Style::Ansi, Mark::Part) => format!("\x1b[1;32m{}\x1b[0m", text),
+            (Style::Ansi, Mark::Isolated) => format!("\x1b[2m{}\x1b[0m", text),
+            (Style::Ansi, Mark::Gear) => format!("\x1b[1;33m{}\x1b[0m", text),
+            (Style::Plain, Mark::Part) => format!("[{}]", text),
+            (Style::Plain, Mark::Isolated) => text.to_string(),
+            (Style::Plain, Mark::Gear) => format!("{{{}}}", text),
+        }
+    }
+}
+
+/// How many numbers a symbol needs around it to match a query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    fn accepts(self, count: usize) -> bool {
+        match self {
+            Arity::Exactly(n) => count == n,
+            Arity::AtLeast(n) => count >= n,
+        }
+    }
+}
+
+/// How the numbers around one matching symbol combine into its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Aggregate {
+    fn apply(self, numbers: &[u32]) -> u64 {
+        let numbers = numbers.iter().map(|n| u64::from(*n));
+
+        match self {
+            Aggregate::Product => numbers.product(),
+            Aggregate::Sum => numbers.sum(),
+            Aggregate::Max => numbers.max().unwrap_or(0),
+        }
+    }
+}
+
+/// Question about the numbers around a symbol, written
+/// `<symbol>:<n>[+]:<product|sum|max>`, `+` meaning at least `n` numbers
+/// rather than exactly `n`. The puzzle's gears are `*:2:product`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Query {
+    pub symbol: char,
+    pub arity: Arity,
+    pub aggregate: Aggregate,
+}
+
+impl Query {
+    pub fn gears() -> Self {
+        Query {
+            symbol: '*',
+            arity: Arity::Exactly(2),
+            aggregate: Aggregate::Product,
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = String;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        let expected = || {
+            format!(
+                "expected '<symbol>:<n>[+]:<product|sum|max>', found '{}'",
+                query
+            )
+        };
+
+        let mut chars = query.chars();
+        let symbol = chars.next().ok_or_else(expected)?;
+        let (count, aggregate) = chars
+            .as_str()
+            .strip_prefix(':')
+            .and_then(|rest| rest.split_once(':'))
+            .ok_or_else(expected)?;
+
+        if !is_symbol(symbol) {
+            return Err(format!("'{}' is not a symbol", symbol));
+        }
+
+        let arity = match count.strip_suffix('+') {
+            Some(n) => Arity::AtLeast(n.parse().map_err(|_| expected())?),
+            None => Arity::Exactly(count.parse().map_err(|_| expected())?),
+        };
+
+        let aggregate = match aggregate {
+            "product" => Aggregate::Product,
+            "sum" => Aggregate::Sum,
+            "max" => Aggregate::Max,
+            _ => return Err(expected()),
+        };
+
+        Ok(Query {
+            symbol,
+            arity,
+            aggregate,
+        })
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (count, plus) = match self.arity {
+            Arity::Exactly(n) => (n, ""),
+            Arity::AtLeast(n) => (n, "+"),
+        };
+
+        let aggregate = match self.aggregate {
+            Aggregate::Product => "product",
+            Aggregate::Sum => "sum",
+            Aggregate::Max => "max",
+        };
+
+        write!(f, "{}:{}{}:{}", self.symbol, count, plus, aggregate)
+    }
+}
+
+fn puzzle1(input: &Schematic) -> u64 {
+    input
+        .numbers
+        .iter()
+        .filter(|(_, span)| input.is_part_number(span))
+        .map(|(value, _)| u64::from(*value))
+        .sum()
+}
+
+fn puzzle2(input: &Schematic) -> u64 {
+    input.answer(&Query::gears())
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::read_input;
+
+    const TEST_FILE: &str = "inputs/day3_test.txt";
+
+    #[test]
+    fn puzzle1_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+
+        assert_eq!(puzzle1(&test_input), 4361)
+    }
+
+    #[test]
+    fn puzzle2_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+
+        assert_eq!(puzzle2(&test_input), 467835);
+    }
+
+    #[test]
+    fn span_test() {
+        let schematic = parse("...12\n34*..\n").unwrap();
+        let spans: Vec<(u32, usize, usize, usize)> = schematic
+            .numbers
+            .iter()
+            .map(|(value, span)| (*value, span.row, span.columns.start, span.columns.end))
+            .collect();
+
+        assert_eq!(spans, vec![(12, 0, 3, 5), (34, 1, 0, 2)]);
+        assert_eq!(puzzle1(&schematic), 46);
+        assert_eq!(puzzle2(&schematic), 408);
+    }
+
+    #[test]
+    fn query_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+
+        assert_eq!("*:2:product".parse(), Ok(Query::gears()));
+
+        let query: Query = "*:1+:max".parse().unwrap();
+
+        assert_eq!(query.to_string(), "*:1+:max");
+        assert_eq!(test_input.matches(&query).len(), 3);
+        assert_eq!(test_input.answer(&query), 467 + 755 + 617);
+
+        let query: Query = "*:1:sum".parse().unwrap();
+
+        assert_eq!(test_input.matches(&query), vec![((4, 3), vec![617])]);
+        assert_eq!(test_input.answer(&"#:1:sum".parse().unwrap()), 633);
+
+        assert!("*:two:sum".parse::<Query>().is_err());
+        assert!("7:2:sum".parse::<Query>().is_err());
+        assert!("*:2".parse::<Query>().is_err());
+    }
+
+    #[test]
+    fn render_test() {
+        let schematic = parse("12.5\n.*..\n.3.#\n").unwrap();
+
+        assert_eq!(schematic.render(Style::Plain), "{12}.5\n.{*}..\n.{3}.#\n");
+
+        let schematic = parse("12.5\n.*..\n...#\n").unwrap();
+
+        assert_eq!(schematic.render(Style::Plain), "[12].5\n.*..\n...#\n");
+        assert_eq!(
+            schematic.render(Style::Ansi).lines().next(),
+            Some("\x1b[1;32m12\x1b[0m.\x1b[2m5\x1b[0m")
+        );
+    }
+
+    /// Part 1 and 2 worked out cell by cell, straight from the puzzle text.
+    fn reference(rows: &[Vec<char>]) -> (u64, u64) {
+        let get = |row: isize, column: isize| -> char {
+            if row < 0 || column < 0 {
+                return '.';
+            }
+
+            rows.get(row as usize)
+                .and_then(|cells| cells.get(column as usize))
+                .copied()
+                .unwrap_or('.')
+        };
+
+        // Walks left to the first digit of the number covering a cell.
+        let number_at = |row: isize, mut column: isize| -> (isize, u32) {
+            while get(row, column - 1).is_ascii_digit() {
+                column -= 1;
+            }
+
+            let start = column;
+            let mut value = 0;
+
+            while let Some(digit) = get(row, column).to_digit(10) {
+                value = value * 10 + digit;
+                column += 1;
+            }
+
+            (start, value)
+        };
+
+        let mut part1 = 0;
+        let mut part2 = 0;
+
+        for (row, cells) in rows.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                let (row, column) = (row as isize, column as isize);
+                let around = |row: isize, column: isize| {
+                    (-1..=1).flat_map(move |dr| (-1..=1).map(move |dc| (row + dr, column + dc)))
+                };
+
+                if cell.is_ascii_digit() && !get(row, column - 1).is_ascii_digit() {
+                    let (_, value) = number_at(row, column);
+                    let len = value.to_string().len() as isize;
+                    let touches_symbol = (column..column + len)
+                        .flat_map(|c| around(row, c))
+                        .any(|(r, c)| is_symbol(get(r, c)));
+
+                    if touches_symbol {
+                        part1 += u64::from(value);
+                    }
+                }
+
+                if *cell == '*' {
+                    let mut numbers: Vec<(isize, isize, u32)> = around(row, column)
+                        .filter(|(r, c)| get(*r, *c).is_ascii_digit())
+                        .map(|(r, c)| {
+                            let (start, value) = number_at(r, c);
+                            (r, start, value)
+                        })
+                        .collect();
+
+                    numbers.sort();
+                    numbers.dedup();
+
+                    if numbers.len() == 2 {
+                        part2 += u64::from(numbers[0].2 * numbers[1].2);
+                    }
+                }
+            }
+        }
+
+        (part1, part2)
+    }
+
+    /// A dot, a symbol, or a number of up to 3 digits closed by a non-digit,
+    /// so rows stay within the puzzle's number sizes.
+    fn token() -> impl Strategy<Value = String> {
+        let closing = prop::sample::select(vec!['.', '*', '#']);
+
+        prop_oneof![
+            3 => Just(".".to_string()),
+            2 => prop::sample::select(vec!['*', '#', '+', '$']).prop_map(String::from),
+            2 => (1u32..1000, closing).prop_map(|(n, c)| format!("{}{}", n, c)),
+        ]
+    }
+
+    /// Rows cut to width, so numbers also run into the right edge.
+    fn schematic() -> impl Strategy<Value = Vec<Vec<char>>> {
+        (1usize..16, 1usize..12).prop_flat_map(|(width, height)| {
+            let row = prop::collection::vec(token(), width)
+                .prop_map(move |tokens| tokens.concat().chars().take(width).collect());
+
+            prop::collection::vec(row, height)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn reference_test(rows in schematic()) {
+            let lines: Vec<String> = rows.iter().map(|cells| cells.iter().collect()).collect();
+            let schematic = parse(&lines.join("\n")).unwrap();
+
+            prop_assert_eq!((puzzle1(&schematic), puzzle2(&schematic)), reference(&rows));
+        }
+    }
+}
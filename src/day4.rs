@@ -0,0 +1,207 @@
+use regex::Regex;
+
+use crate::{parse_number, ParseError, Solution};
+
+/// Set of numbers below 128, one bit each.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NumberSet(u128);
+
+impl NumberSet {
+    pub const CAPACITY: u32 = u128::BITS;
+
+    /// Adds `number`, returning whether it was not in the set yet.
+    pub fn insert(&mut self, number: u32) -> bool {
+        assert!(
+            number < Self::CAPACITY,
+            "{} does not fit in the set",
+            number
+        );
+
+        let new = !self.contains(number);
+        self.0 |= 1 << number;
+        new
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        number < Self::CAPACITY && self.0 & (1 << number) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn intersection(&self, other: &NumberSet) -> NumberSet {
+        NumberSet(self.0 & other.0)
+    }
+}
+
+#[derive(Debug)]
+pub struct Card {
+    pub id: u32,
+    pub winners: NumberSet,
+    pub have: NumberSet,
+}
+
+impl Card {
+    /// How many of the numbers we have are winning ones.
+    pub fn matches(&self) -> usize {
+        self.winners.intersection(&self.have).len()
+    }
+}
+
+type Cards = Vec<Card>;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Cards;
+    type Output = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        puzzle1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output {
+        puzzle2(input)
+    }
+}
+
+fn parse(input: &str) -> Result<Cards, ParseError> {
+    let re = Regex::new(r"Card\s+(?<card>\d+):(?<results>.*)").unwrap();
+
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_index, l)| {
+            let caps = re
+                .captures(l)
+                .ok_or_else(|| ParseError::new(line_index, l, l, "expected 'Card <id>: ...'"))?;
+
+            let id = parse_number(line_index, l, &caps["card"])?;
+            let results = caps.name("results").unwrap().as_str();
+
+            let (winner_str, card_str) = results.split_once('|').ok_or_else(|| {
+                ParseError::new(line_index, l, results, "expected '<winners> | <numbers>'")
+            })?;
+
+            let winners = build_card(line_index, l, winner_str)?;
+            let have = build_card(line_index, l, card_str)?;
+
+            Ok(Card { id, winners, have })
+        })
+        .collect()
+}
+
+fn build_card(line_index: usize, line: &str, card_str: &str) -> Result<NumberSet, ParseError> {
+    let mut numbers = NumberSet::default();
+
+    for v in card_str.split_ascii_whitespace() {
+        let number: u32 = parse_number(line_index, line, v)?;
+
+        if number >= NumberSet::CAPACITY {
+            return Err(ParseError::new(
+                line_index,
+                line,
+                v,
+                format!("expected a number below {}", NumberSet::CAPACITY),
+            ));
+        }
+
+        if !numbers.insert(number) {
+            return Err(ParseError::new(line_index, line, v, "number listed twice"));
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// A card can match all 128 numbers, so the points need a `u128`.
+fn puzzle1(input: &Cards) -> u128 {
+    const BASE: u128 = 2;
+
+    input
+        .iter()
+        .map(|card| match card.matches() {
+            0 => 0,
+            matches => BASE.pow(matches as u32 - 1),
+        })
+        .sum()
+}
+
+fn puzzle2(input: &Cards) -> u128 {
+    let mut extra_list: Vec<u128> = vec![0; input.len()];
+
+    input
+        .iter()
+        .map(Card::matches)
+        .enumerate()
+        .for_each(|(i, wins)| {
+            extra_list[i] += 1;
+            let copies = extra_list[i];
+
+            (i + 1..(i + 1 + wins).min(input.len()))
+                .for_each(|extra_index| extra_list[extra_index] += copies)
+        });
+
+    extra_list.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    const TEST_FILE: &str = "inputs/day4_test.txt";
+
+    #[test]
+    fn puzzle1_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+
+        assert_eq!(puzzle1(&test_input), 13);
+
+        let numbers: Vec<String> = (0..=70).map(|n| n.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let card = parse(&format!("Card 1: {} | {}", numbers, numbers)).unwrap();
+
+        assert_eq!(puzzle1(&card), 1 << 70);
+    }
+
+    #[test]
+    fn puzzle2_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+
+        assert_eq!(puzzle2(&test_input), 30);
+
+        // Wins past the last card win nothing.
+        assert_eq!(puzzle2(&parse("Card 1: 1 2 3 | 1 2 3").unwrap()), 1);
+    }
+
+    #[test]
+    fn card_test() {
+        let cards = parse("Card  7: 1 99 127 | 127 2 99 0").unwrap();
+
+        assert_eq!(cards[0].id, 7);
+        assert_eq!(cards[0].matches(), 2);
+        assert!(cards[0].have.contains(0));
+        assert!(!cards[0].winners.contains(200));
+
+        let error = parse("Card 1: 1 128 | 2").unwrap_err();
+
+        assert_eq!((error.column, error.snippet.as_str()), (11, "128"));
+
+        let error = parse("Card 1: 1 | 2 3 2").unwrap_err();
+
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (17, "number listed twice")
+        );
+    }
+}
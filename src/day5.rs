@@ -0,0 +1,676 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+};
+
+use crate::{
+    interval::{merge, Interval},
+    parse_number, ParseError, Solution,
+};
+
+type AlmanacMap = Vec<(usize, usize, usize)>;
+
+#[derive(Debug)]
+struct CategoryMap {
+    source: String,
+    dest: String,
+    ranges: AlmanacMap,
+}
+
+#[derive(Debug)]
+pub struct SeedMapper {
+    seeds: Vec<usize>,
+    maps: Vec<CategoryMap>,
+}
+
+/// Something in an almanac map that `next_step` silently papers over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// Two ranges claim the same sources; the first one listed wins.
+    SourceOverlap(Interval),
+    /// Two ranges send sources to the same destinations, so the map cannot
+    /// be inverted.
+    DestinationOverlap(Interval),
+    /// Values between the ranges that no range covers and map to themselves.
+    Gap(Interval),
+}
+
+impl Issue {
+    pub fn is_ambiguous(&self) -> bool {
+        !matches!(self, Issue::Gap(_))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub map: String,
+    pub issue: Issue,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.issue {
+            Issue::SourceOverlap(overlap) => {
+                write!(f, "{}: source ranges overlap on {}", self.map, overlap)
+            }
+            Issue::DestinationOverlap(overlap) => {
+                write!(f, "{}: destination ranges overlap on {}", self.map, overlap)
+            }
+            Issue::Gap(gap) => write!(f, "{}: no range covers {}", self.map, gap),
+        }
+    }
+}
+
+impl SeedMapper {
+    /// Overlapping and missing ranges of every map, in almanac order.
+    pub fn validate(&self) -> Vec<Finding> {
+        self.maps
+            .iter()
+            .flat_map(|map| {
+                validate_map(&map.ranges).into_iter().map(|issue| Finding {
+                    map: format!("{}-to-{}", map.source, map.dest),
+                    issue,
+                })
+            })
+            .collect()
+    }
+
+    /// Maps to apply in order to turn `from` values into `to` values, found by
+    /// a breadth-first walk over the `<source>-to-<dest>` edges.
+    fn chain(&self, from: &str, to: &str) -> Option<Vec<&AlmanacMap>> {
+        let mut reached_by: HashMap<&str, Option<&CategoryMap>> = HashMap::from([(from, None)]);
+        let mut queue: VecDeque<&str> = VecDeque::from([from]);
+
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                let mut chain: Vec<&AlmanacMap> = vec![];
+                let mut current = category;
+
+                while let Some(Some(map)) = reached_by.get(current) {
+                    chain.push(&map.ranges);
+                    current = &map.source;
+                }
+
+                chain.reverse();
+                return Some(chain);
+            }
+
+            for map in self.maps.iter().filter(|m| m.source == category) {
+                if !reached_by.contains_key(map.dest.as_str()) {
+                    reached_by.insert(&map.dest, Some(map));
+                    queue.push_back(&map.dest);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Converts a `from` value (e.g. a soil) into its `to` category (e.g. a
+    /// humidity), or `None` if the almanac does not link the two.
+    pub fn resolve(&self, from: &str, to: &str, value: usize) -> Option<usize> {
+        let chain = self.chain(from, to)?;
+
+        Some(chain.iter().fold(value, |value, map| next_step(map, value)))
+    }
+
+    /// Collapses every map between `from` and `to` into a single one.
+    pub fn flatten(&self, from: &str, to: &str) -> Option<AlmanacMap> {
+        let chain = self.chain(from, to)?;
+
+        Some(
+            chain
+                .iter()
+                .fold(vec![], |flattened, map| compose(&flattened, map)),
+        )
+    }
+
+    /// Every `from` value that ends up as `value` once converted to `to`,
+    /// e.g. which seeds land at a given location.
+    pub fn reverse_resolve(&self, from: &str, to: &str, value: usize) -> Option<Vec<usize>> {
+        let flattened = self.flatten(from, to)?;
+
+        Some(preimages(&invert(&flattened), value))
+    }
+
+    pub fn resolve_intervals(
+        &self,
+        from: &str,
+        to: &str,
+        intervals: Vec<Interval>,
+    ) -> Option<Vec<Interval>> {
+        let chain = self.chain(from, to)?;
+
+        Some(chain.iter().fold(merge(intervals), |intervals, map| {
+            map_intervals(map, intervals)
+        }))
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = SeedMapper;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        puzzle1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output {
+        puzzle2(input)
+    }
+}
+
+fn parse(input: &str) -> Result<SeedMapper, ParseError> {
+    let mut seed_mapper = SeedMapper {
+        seeds: vec![],
+        maps: vec![],
+    };
+
+    for (line_index, line) in input.lines().enumerate().filter(|(_, l)| !l.is_empty()) {
+        match line {
+            _ if line.ends_with("map:") => {
+                let (source, dest) = line
+                    .strip_suffix(" map:")
+                    .and_then(|header| header.split_once("-to-"))
+                    .filter(|(source, dest)| is_category(source) && is_category(dest))
+                    .ok_or_else(|| {
+                        ParseError::new(
+                            line_index,
+                            line,
+                            line,
+                            "expected '<source>-to-<dest> map:'",
+                        )
+                    })?;
+
+                if seed_mapper
+                    .maps
+                    .iter()
+                    .any(|m| m.source == source && m.dest == dest)
+                {
+                    return Err(ParseError::new(line_index, line, line, "duplicate map"));
+                }
+
+                seed_mapper.maps.push(CategoryMap {
+                    source: source.to_string(),
+                    dest: dest.to_string(),
+                    ranges: vec![],
+                });
+            }
+            _ if line.starts_with("seeds") => {
+                let tokens: Vec<&str> = line.split_ascii_whitespace().skip(1).collect();
+
+                seed_mapper.seeds = tokens
+                    .iter()
+                    .map(|s| parse_number(line_index, line, s))
+                    .collect::<Result<_, _>>()?;
+
+                match tokens.last() {
+                    None => {
+                        return Err(ParseError::new(
+                            line_index,
+                            line,
+                            &line[line.len()..],
+                            "expected at least one seed",
+                        ))
+                    }
+                    Some(last) if tokens.len() % 2 == 1 => {
+                        return Err(ParseError::new(
+                            line_index,
+                            line,
+                            last,
+                            "expected seeds in '<start> <length>' pairs",
+                        ))
+                    }
+                    _ => (),
+                }
+
+                for (pair, tokens) in seed_mapper.seeds.chunks(2).zip(tokens.chunks(2)) {
+                    if pair[1] == 0 {
+                        return Err(ParseError::new(
+                            line_index,
+                            line,
+                            tokens[1],
+                            "expected a non-empty seed range",
+                        ));
+                    }
+
+                    if pair[0].checked_add(pair[1]).is_none() {
+                        return Err(ParseError::new(
+                            line_index,
+                            line,
+                            tokens[1],
+                            "range overflows",
+                        ));
+                    }
+                }
+            }
+            _ => {
+                let tokens: Vec<&str> = line.split_ascii_whitespace().collect();
+                let values: Vec<usize> = tokens
+                    .iter()
+                    .map(|s| parse_number(line_index, line, s))
+                    .collect::<Result<_, _>>()?;
+
+                let [dest, source, range] = values[..] else {
+                    return Err(ParseError::new(
+                        line_index,
+                        line,
+                        line,
+                        "expected '<destination> <source> <length>'",
+                    ));
+                };
+
+                let Some(current_map) = seed_mapper.maps.last_mut() else {
+                    return Err(ParseError::new(
+                        line_index,
+                        line,
+                        line,
+                        "range outside of a map",
+                    ));
+                };
+
+                if source.max(dest).checked_add(range).is_none() {
+                    return Err(ParseError::new(
+                        line_index,
+                        line,
+                        tokens[2],
+                        "range overflows",
+                    ));
+                }
+
+                current_map.ranges.push((source, dest, range));
+            }
+        }
+    }
+
+    if seed_mapper.seeds.is_empty() {
+        return Err(ParseError::end_of_input(
+            input.lines().count(),
+            "missing 'seeds:' line",
+        ));
+    }
+
+    if seed_mapper.chain("seed", "location").is_none() {
+        return Err(ParseError::end_of_input(
+            input.lines().count(),
+            "no chain of maps from seed to location",
+        ));
+    }
+
+    Ok(seed_mapper)
+}
+
+fn is_category(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn puzzle1(input: &SeedMapper) -> usize {
+    input
+        .seeds
+        .iter()
+        .map(|seed| input.resolve("seed", "location", *seed).unwrap())
+        .min()
+        .unwrap()
+}
+
+fn puzzle2(input: &SeedMapper) -> usize {
+    let seeds: Vec<Interval> = input
+        .seeds
+        .chunks(2)
+        .map(|chunk| Interval::with_len(chunk[0], chunk[1]))
+        .collect();
+
+    input
+        .resolve_intervals("seed", "location", seeds)
+        .unwrap()
+        .iter()
+        .map(|location| location.start)
+        .min()
+        .unwrap()
+}
+
+fn next_step(map: &AlmanacMap, value: usize) -> usize {
+    match map
+        .iter()
+        .find(|(source, _dest, range_length)| value >= *source && value < *source + *range_length)
+    {
+        Some((source, dest, _range_length)) => dest + (value - source),
+        None => value,
+    }
+}
+
+/// Pushes whole intervals through `map`, splitting them at range boundaries.
+/// Like `next_step`, the first range containing a value wins and values
+/// outside every range map to themselves.
+fn map_intervals(map: &AlmanacMap, intervals: Vec<Interval>) -> Vec<Interval> {
+    let mut pending = intervals;
+    let mut mapped: Vec<Interval> = vec![];
+
+    while let Some(interval) = pending.pop() {
+        let hit = map.iter().find_map(|(source, dest, range_length)| {
+            interval
+                .intersection(&Interval::with_len(*source, *range_length))
+                .map(|overlap| (overlap, source, dest))
+        });
+
+        match hit {
+            Some((overlap, source, dest)) => {
+                mapped.push(overlap.translate(*source, *dest));
+
+                let (before, after) = interval.difference(&overlap);
+                pending.extend(before.into_iter().chain(after));
+            }
+            None => mapped.push(interval),
+        }
+    }
+
+    merge(mapped)
+}
+
+fn validate_map(map: &AlmanacMap) -> Vec<Issue> {
+    let sources: Vec<Interval> = map
+        .iter()
+        .map(|(source, _dest, range_length)| Interval::with_len(*source, *range_length))
+        .collect();
+    let dests: Vec<Interval> = map
+        .iter()
+        .map(|(_source, dest, range_length)| Interval::with_len(*dest, *range_length))
+        .collect();
+
+    let mut issues: Vec<Issue> = vec![];
+
+    for (i, source) in sources.iter().enumerate() {
+        for other in sources[i + 1..].iter() {
+            if let Some(overlap) = source.intersection(other) {
+                issues.push(Issue::SourceOverlap(overlap));
+            }
+        }
+    }
+
+    for (i, dest) in dests.iter().enumerate() {
+        for other in dests[i + 1..].iter() {
+            if let Some(overlap) = dest.intersection(other) {
+                issues.push(Issue::DestinationOverlap(overlap));
+            }
+        }
+    }
+
+    for pair in merge(sources).windows(2) {
+        issues.push(Issue::Gap(Interval::new(pair[0].end, pair[1].start)));
+    }
+
+    issues
+}
+
+/// Splits `map` into sorted, non-overlapping `(source, dest start)` pieces
+/// covering every value, resolving overlaps the way `next_step` does and
+/// filling the gaps with identity pieces.
+fn pieces(map: &AlmanacMap) -> Vec<(Interval, usize)> {
+    let mut covered: Vec<Interval> = vec![];
+    let mut pieces: Vec<(Interval, usize)> = vec![];
+
+    for (source, dest, range_length) in map {
+        let range = Interval::with_len(*source, *range_length);
+        let mut uncovered = vec![range];
+
+        for taken in covered.iter() {
+            uncovered = uncovered
+                .into_iter()
+                .flat_map(|interval| match interval.intersection(taken) {
+                    Some(_) => {
+                        let (before, after) = interval.difference(taken);
+                        before.into_iter().chain(after).collect()
+                    }
+                    None => vec![interval],
+                })
+                .collect();
+        }
+
+        pieces.extend(
+            uncovered
+                .into_iter()
+                .map(|interval| (interval, interval.start - source + dest)),
+        );
+
+        covered = merge([covered, vec![range]].concat());
+    }
+
+    let mut gap_start = 0;
+
+    for taken in covered
+        .iter()
+        .chain([&Interval::new(usize::MAX, usize::MAX)])
+    {
+        if gap_start < taken.start {
+            pieces.push((Interval::new(gap_start, taken.start), gap_start));
+        }
+
+        gap_start = taken.end;
+    }
+
+    pieces.sort();
+    pieces
+}
+
+/// Single map equivalent to applying `first` and then `second`.
+pub fn compose(first: &AlmanacMap, second: &AlmanacMap) -> AlmanacMap {
+    let second = pieces(second);
+    let mut composed: AlmanacMap = vec![];
+
+    for (source, dest) in pieces(first) {
+        let image = source.translate(source.start, dest);
+
+        for (next_source, next_dest) in second.iter() {
+            if let Some(overlap) = image.intersection(next_source) {
+                let from = overlap.start - dest + source.start;
+                let to = overlap.start - next_source.start + next_dest;
+
+                match composed.last_mut() {
+                    Some((last_from, last_to, length))
+                        if *last_from + *length == from && *last_to + *length == to =>
+                    {
+                        *length += overlap.len()
+                    }
+                    _ if from == to => (),
+                    _ => composed.push((from, to, overlap.len())),
+                }
+            }
+        }
+    }
+
+    composed
+}
+
+/// Destination to source map. Every piece is listed, identity stretches
+/// included, so that `preimages` finds all sources of a value even when
+/// `map` sends several of them to the same place.
+pub fn invert(map: &AlmanacMap) -> AlmanacMap {
+    let mut inverted: AlmanacMap = pieces(map)
+        .into_iter()
+        .map(|(source, dest)| (dest, source.start, source.len()))
+        .collect();
+
+    inverted.sort();
+    inverted
+}
+
+/// Every value `map` sends to `value` through any of its pieces.
+pub fn preimages(map: &AlmanacMap, value: usize) -> Vec<usize> {
+    map.iter()
+        .filter(|(source, _dest, range_length)| {
+            Interval::with_len(*source, *range_length).contains(value)
+        })
+        .map(|(source, dest, _range_length)| dest + (value - source))
+        .collect()
+}
+
+/// Renders `map` in the almanac's own `<dest> <source> <length>` format.
+pub fn format_map(from: &str, to: &str, map: &AlmanacMap) -> String {
+    let mut table = format!("{}-to-{} map:\n", from, to);
+
+    for (source, dest, range_length) in map {
+        table += &format!("{} {} {}\n", dest, source, range_length);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    const TEST_FILE: &str = "inputs/day5_test.txt";
+
+    #[test]
+    fn puzzle1_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+
+        assert_eq!(puzzle1(&test_input), 35)
+    }
+
+    #[test]
+    fn puzzle2_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+
+        assert_eq!(puzzle2(&test_input), 46)
+    }
+
+    #[test]
+    fn map_intervals_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+        let seed_soil = &test_input.maps[0].ranges;
+
+        for start in 40..100 {
+            for len in [1, 7, 30] {
+                let mapped = map_intervals(seed_soil, vec![Interval::with_len(start, len)]);
+                let expected: Vec<Interval> = merge(
+                    (start..start + len)
+                        .map(|seed| Interval::with_len(next_step(seed_soil, seed), 1))
+                        .collect(),
+                );
+
+                assert_eq!(mapped, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn resolve_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+
+        // Seed 79 goes through soil 81 up to humidity 78 in the puzzle example.
+        assert_eq!(test_input.resolve("soil", "humidity", 81), Some(78));
+        assert_eq!(test_input.resolve("seed", "seed", 79), Some(79));
+        assert_eq!(test_input.resolve("location", "seed", 82), None);
+
+        let extended =
+            parse("seeds: 5 6\n\nseed-to-soil map:\n10 0 10\n\nsoil-to-location map:\n0 10 10\n")
+                .unwrap();
+
+        assert_eq!(puzzle1(&extended), 5);
+    }
+
+    #[test]
+    fn compose_invert_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+        let flattened = test_input.flatten("seed", "location").unwrap();
+
+        for seed in 0..120 {
+            let location = test_input.resolve("seed", "location", seed).unwrap();
+
+            assert_eq!(next_step(&flattened, seed), location);
+            assert!(preimages(&invert(&flattened), location).contains(&seed));
+        }
+
+        assert_eq!(
+            test_input.reverse_resolve("seed", "location", 46),
+            Some(vec![82])
+        );
+
+        // 98 and 50 both land on 50 when the map is not injective.
+        let mut sources = preimages(&invert(&vec![(98, 50, 2)]), 50);
+        sources.sort();
+
+        assert_eq!(sources, vec![50, 98]);
+    }
+
+    #[test]
+    fn validate_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+
+        assert!(test_input
+            .validate()
+            .iter()
+            .all(|f| !f.issue.is_ambiguous()));
+
+        let almanac =
+            parse("seeds: 1 1\n\nseed-to-location map:\n0 10 5\n3 12 2\n100 20 5\n").unwrap();
+
+        assert_eq!(
+            almanac
+                .validate()
+                .iter()
+                .map(|finding| finding.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "seed-to-location: source ranges overlap on [12, 14)",
+                "seed-to-location: destination ranges overlap on [3, 5)",
+                "seed-to-location: no range covers [15, 20)",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let error = parse("seeds: 79 14\n\nseed-to-soil map:\n50 98\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.snippet, "50 98");
+
+        let maps = "\nseed-to-location map:\n0 0 1\n";
+
+        let error = parse(&format!("seeds: 1 2 3\n{}", maps)).unwrap_err();
+
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (1, 12, "3")
+        );
+
+        let error = parse(&format!("seeds:\n{}", maps)).unwrap_err();
+
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (1, 7, "")
+        );
+
+        let error = parse(maps).unwrap_err();
+
+        assert_eq!(error.message, "missing 'seeds:' line");
+
+        let error = parse(&format!("seeds: 18446744073709551615 5\n{}", maps)).unwrap_err();
+
+        assert_eq!(
+            (error.snippet.as_str(), error.message.as_str()),
+            ("5", "range overflows")
+        );
+
+        let error = parse(&format!("seeds: 1 2 5 0\n{}", maps)).unwrap_err();
+
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (14, "expected a non-empty seed range")
+        );
+
+        let error =
+            parse("seeds: 1 1\n\nseed-to-location map:\n0 18446744073709551615 2\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (4, 24));
+    }
+}
@@ -0,0 +1,332 @@
+use std::{fmt, str::Lines};
+
+use num_bigint::BigUint;
+
+use crate::{parse_number, ParseError, Solution};
+
+type Races = (Vec<u64>, Vec<u64>);
+
+/// Count or concatenated value that stays a native `u128` until it no
+/// longer fits, then moves to arbitrary precision instead of wrapping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Number {
+    Native(u128),
+    Big(BigUint),
+}
+
+impl Number {
+    fn to_big(&self) -> BigUint {
+        match self {
+            Number::Native(value) => BigUint::from(*value),
+            Number::Big(value) => value.clone(),
+        }
+    }
+
+    fn mul(self, other: Number) -> Number {
+        match (&self, &other) {
+            (Number::Native(a), Number::Native(b)) => match a.checked_mul(*b) {
+                Some(product) => Number::Native(product),
+                None => Number::Big(BigUint::from(*a) * b),
+            },
+            _ => Number::Big(self.to_big() * other.to_big()),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Native(value) => write!(f, "{}", value),
+            Number::Big(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Races;
+    type Output = Number;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        puzzle1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output {
+        puzzle2(input)
+    }
+}
+
+fn parse(input: &str) -> Result<Races, ParseError> {
+    let mut lines = input.lines();
+
+    let race_times: Vec<u64> = parse_next_line(&mut lines, 0, "Time")?;
+    let record_times: Vec<u64> = parse_next_line(&mut lines, 1, "Distance")?;
+
+    if race_times.len() != record_times.len() {
+        let line = input.lines().nth(1).unwrap();
+
+        return Err(ParseError::new(
+            1,
+            line,
+            line,
+            format!("expected {} distances, one per race", race_times.len()),
+        ));
+    }
+
+    Ok((race_times, record_times))
+}
+
+fn parse_next_line(
+    lines: &mut Lines,
+    line_index: usize,
+    label: &str,
+) -> Result<Vec<u64>, ParseError> {
+    let line = lines.next().ok_or_else(|| {
+        ParseError::end_of_input(line_index, format!("expected '{}:' line", label))
+    })?;
+
+    let (name, values) = line.split_once(':').ok_or_else(|| {
+        ParseError::new(
+            line_index,
+            line,
+            line,
+            format!("expected '{}: <values>'", label),
+        )
+    })?;
+
+    if name.trim() != label {
+        return Err(ParseError::new(
+            line_index,
+            line,
+            name.trim(),
+            format!("expected '{}'", label),
+        ));
+    }
+
+    let values: Vec<u64> = values
+        .split_ascii_whitespace()
+        .map(|v| parse_number(line_index, line, v))
+        .collect::<Result<_, _>>()?;
+
+    if values.is_empty() {
+        return Err(ParseError::new(
+            line_index,
+            line,
+            line,
+            "expected at least one value",
+        ));
+    }
+
+    Ok(values)
+}
+
+fn puzzle1(input: &Races) -> Number {
+    let (race_times, record_times) = input;
+
+    race_times
+        .iter()
+        .zip(record_times)
+        .map(|(race_time, record_time)| {
+            Number::Native(winning_hold_times(*race_time, *record_time as u128) as u128)
+        })
+        .fold(Number::Native(1), Number::mul)
+}
+
+fn puzzle2(input: &Races) -> Number {
+    let (race_times, record_times) = input;
+
+    let race = fold_to_one_time(race_times);
+    let record = fold_to_one_time(record_times);
+
+    match (race, record) {
+        (Number::Native(race), Number::Native(record)) if race <= u64::MAX as u128 => {
+            Number::Native(winning_hold_times(race as u64, record) as u128)
+        }
+        (race, record) => Number::Big(winning_hold_times_big(&race.to_big(), &record.to_big())),
+    }
+}
+
+/// Concatenates the digits of every time, e.g. `[7, 15, 30]` into `71530`.
+fn fold_to_one_time(times: &[u64]) -> Number {
+    times.iter().fold(Number::Native(0), |acc, time| {
+        let digits = time.to_string().len() as u32;
+
+        match acc {
+            Number::Native(value) => 10u128
+                .checked_pow(digits)
+                .and_then(|shift| value.checked_mul(shift))
+                .and_then(|shifted| shifted.checked_add(*time as u128))
+                .map(Number::Native)
+                .unwrap_or_else(|| {
+                    Number::Big(BigUint::from(value) * BigUint::from(10u32).pow(digits) + *time)
+                }),
+            Number::Big(value) => Number::Big(value * BigUint::from(10u32).pow(digits) + *time),
+        }
+    })
+}
+
+/// Number of hold times `h` in `0..=race` with `h * (race - h) > record`.
+///
+/// The winners are the integers strictly between the roots of
+/// `h² - race·h + record = 0`, so the integer square root gives the lower
+/// bound up to rounding, which is then settled by checking the inequality
+/// exactly. Products are done in `u128`, which holds `race²` for any `u64`.
+fn winning_hold_times(race: u64, record: u128) -> u64 {
+    let race = race as u128;
+    let wins = |hold: u128| hold * (race - hold) > record;
+
+    let discriminant = match record
+        .checked_mul(4)
+        .and_then(|four_record| (race * race).checked_sub(four_record))
+    {
+        Some(discriminant) if discriminant > 0 => discriminant,
+        _ => return 0,
+    };
+
+    let mut low = (race - discriminant.isqrt()) / 2;
+
+    while low > 0 && wins(low - 1) {
+        low -= 1;
+    }
+
+    while low <= race / 2 && !wins(low) {
+        low += 1;
+    }
+
+    if low > race / 2 {
+        return 0;
+    }
+
+    // Winners are symmetric around race / 2: every hold in low..=race - low.
+    (race - 2 * low + 1) as u64
+}
+
+/// `winning_hold_times` for races too long to square in `u128`.
+fn winning_hold_times_big(race: &BigUint, record: &BigUint) -> BigUint {
+    let zero = BigUint::from(0u32);
+    let wins = |hold: &BigUint| hold * (race - hold) > *record;
+
+    let square = race * race;
+    let four_record = record * 4u32;
+
+    if square <= four_record {
+        return zero;
+    }
+
+    let half = race / 2u32;
+    let mut low = (race - (square - four_record).sqrt()) / 2u32;
+
+    while low > zero && wins(&(&low - 1u32)) {
+        low -= 1u32;
+    }
+
+    while low <= half && !wins(&low) {
+        low += 1u32;
+    }
+
+    if low > half {
+        return zero;
+    }
+
+    race - low * 2u32 + 1u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    const TEST_FILE: &str = "inputs/day6_test.txt";
+
+    #[test]
+    fn puzzle1_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+
+        assert_eq!(puzzle1(&test_input), Number::Native(288))
+    }
+
+    #[test]
+    fn puzzle2_test() {
+        let test_input = parse(&read_input(TEST_FILE).unwrap()).unwrap();
+
+        assert_eq!(puzzle2(&test_input), Number::Native(71503))
+    }
+
+    #[test]
+    fn winning_hold_times_test() {
+        for race in 0..60u64 {
+            for record in 0..=race * race / 4 + 1 {
+                let expected = (0..=race)
+                    .filter(|hold_time| hold_time * (race - hold_time) > record)
+                    .count() as u64;
+
+                assert_eq!(
+                    winning_hold_times(race, record as u128),
+                    expected,
+                    "{} {}",
+                    race,
+                    record
+                );
+            }
+        }
+
+        let race = 10u64.pow(18);
+        let half = race as u128 / 2;
+
+        assert_eq!(winning_hold_times(race, 0), race - 1);
+        assert_eq!(winning_hold_times(race, u128::MAX), 0);
+        assert_eq!(winning_hold_times(race, half * half - 1), 1);
+        assert_eq!(winning_hold_times(race, half * half), 0);
+        assert_eq!(winning_hold_times(race, (half - 1) * (half + 1)), 1);
+        assert_eq!(winning_hold_times(race, (half - 2) * (half + 2)), 3);
+    }
+
+    #[test]
+    fn big_race_test() {
+        for (race, record) in [(30u64, 200u128), (71530, 940200), (10u64.pow(18), 12345)] {
+            assert_eq!(
+                winning_hold_times_big(&BigUint::from(race), &BigUint::from(record)),
+                BigUint::from(winning_hold_times(race, record))
+            );
+        }
+
+        // Concatenates to a 57 digit race time, far past u128.
+        let max = 10u64.pow(19) - 1;
+        let races = (vec![max, max, max], vec![0, 0, 0]);
+        let race = "9".repeat(57).parse::<BigUint>().unwrap();
+
+        assert_eq!(fold_to_one_time(&races.0), Number::Big(race.clone()));
+        assert_eq!(puzzle2(&races).to_string(), (race - 1u32).to_string());
+        assert_eq!(
+            puzzle1(&races).to_string(),
+            (BigUint::from(max - 1).pow(3)).to_string()
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let error = parse("Time:      7  15   30\n").unwrap_err();
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.message, "expected 'Distance:' line");
+
+        let error = parse("Time: 7 15 30\nDistance: 9 40\n").unwrap_err();
+
+        assert_eq!(error.message, "expected 3 distances, one per race");
+
+        let error = parse("Time: 7 99999999999999999999\nDistance: 9 40\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (1, 9));
+
+        let error = parse("Distance: 9 40 200\nTime: 7 15 30\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.message, "expected 'Time'");
+        assert_eq!(error.snippet, "Distance");
+    }
+}
@@ -0,0 +1,101 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// Malformed puzzle input, pointing at the exact place the parser gave up.
+///
+/// Lines and columns are 1-based. `file` is unknown to the parsers themselves
+/// and gets filled in by whoever loaded the input, through `with_file`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// `token` has to be a slice of `line`, its offset gives the column.
+    pub fn new(line_index: usize, line: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|offset| *offset <= line.len())
+            .unwrap_or(0);
+
+        ParseError {
+            file: None,
+            line: line_index + 1,
+            column: line[..offset].chars().count() + 1,
+            snippet: token.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Error for input that stops before the parser found what it expected.
+    pub fn end_of_input(line_index: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            file: None,
+            line: line_index + 1,
+            column: 1,
+            snippet: String::new(),
+            message: message.into(),
+        }
+    }
+
+    pub fn with_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+
+        if self.snippet.is_empty() {
+            write!(f, " (found end of line)")
+        } else {
+            write!(f, " (found '{}')", self.snippet)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn parse_number<T: FromStr>(
+    line_index: usize,
+    line: &str,
+    token: &str,
+) -> Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::new(line_index, line, token, "expected a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_test() {
+        let line = "Game 1: 3 blue, x red";
+        let error = ParseError::new(4, line, &line[16..17], "expected a number");
+
+        assert_eq!((error.line, error.column), (5, 17));
+        assert_eq!(error.snippet, "x");
+    }
+
+    #[test]
+    fn display_test() {
+        let line = "seeds: 79 1x4";
+        let error = parse_number::<usize>(0, line, &line[10..]).unwrap_err();
+
+        assert_eq!(
+            error.with_file("inputs/day5.txt").to_string(),
+            "inputs/day5.txt:1:11: expected a number (found '1x4')"
+        );
+    }
+}
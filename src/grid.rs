@@ -0,0 +1,286 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::ParseError;
+
+/// `(row, column)`, both 0-based.
+pub type Position = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rectangular grid of cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {}x{} grid needs {} cells",
+            width,
+            height,
+            width * height
+        );
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Reads one row per line and one cell per character, turning each
+    /// character into a cell with `cell` or rejecting it when that gives
+    /// `None`. Every line must be as long as the first one.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (line_index, line) in input.lines().enumerate() {
+            let mut columns = 0;
+
+            for (offset, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    let token = &line[offset..offset + c.len_utf8()];
+
+                    ParseError::new(line_index, line, token, "unexpected character")
+                })?;
+
+                cells.push(value);
+                columns += 1;
+            }
+
+            match width {
+                None => width = Some(columns),
+                Some(width) if width != columns => {
+                    return Err(ParseError::new(
+                        line_index,
+                        line,
+                        line,
+                        format!("expected a row of {} cells", width),
+                    ))
+                }
+                _ => (),
+            }
+
+            height += 1;
+        }
+
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[self.offset(position)])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if !self.contains(position) {
+            return None;
+        }
+
+        let offset = self.offset(position);
+
+        Some(&mut self.cells[offset])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The up to 4 positions sharing an edge with `position`.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.offsets(position, &ORTHOGONAL)
+    }
+
+    /// The up to 8 positions sharing an edge or a corner with `position`.
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.offsets(position, &SURROUNDING)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "row {} is out of bounds", row);
+
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(column < self.width, "column {} is out of bounds", column);
+
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    /// Cells from `position` going down and to the right.
+    pub fn diagonal(&self, position: Position) -> impl Iterator<Item = &T> {
+        self.walk(position, (1, 1))
+    }
+
+    /// Cells from `position` going down and to the left.
+    pub fn anti_diagonal(&self, position: Position) -> impl Iterator<Item = &T> {
+        self.walk(position, (1, -1))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    fn offset(&self, (row, column): Position) -> usize {
+        row * self.width + column
+    }
+
+    fn step(&self, (row, column): Position, (dr, dc): (isize, isize)) -> Option<Position> {
+        let position = (row.checked_add_signed(dr)?, column.checked_add_signed(dc)?);
+
+        self.contains(position).then_some(position)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        position: Position,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |offset| self.step(position, *offset))
+    }
+
+    fn walk(&self, position: Position, direction: (isize, isize)) -> impl Iterator<Item = &T> {
+        let start = self.contains(position).then_some(position);
+
+        std::iter::successors(start, move |position| self.step(*position, direction))
+            .map(|position| &self[position])
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} is outside of a {}x{} grid",
+                position, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} is outside of a {}x{} grid",
+                position, width, height
+            )
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors_test() {
+        let grid = Grid::parse("abc\ndef\n", Some).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+
+        let corner: Vec<Position> = grid.neighbors8((0, 0)).collect();
+
+        assert_eq!(corner, vec![(0, 1), (1, 0), (1, 1)]);
+
+        let middle: Vec<char> = grid.neighbors4((1, 1)).map(|p| grid[p]).collect();
+
+        assert_eq!(middle, vec!['b', 'd', 'f']);
+        assert_eq!(grid.neighbors8((0, 1)).count(), 5);
+    }
+
+    #[test]
+    fn lines_test() {
+        let grid = Grid::parse("123\n456\n789\n", |c| c.to_digit(10)).unwrap();
+
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6, 9]);
+        assert_eq!(
+            grid.diagonal((0, 1)).copied().collect::<Vec<_>>(),
+            vec![2, 6]
+        );
+        assert_eq!(
+            grid.anti_diagonal((0, 2)).copied().collect::<Vec<_>>(),
+            vec![3, 5, 7]
+        );
+        assert_eq!(grid.map(|n| n % 2).to_string(), "101\n010\n101\n");
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let error = Grid::parse("123\n45\n", |c| c.to_digit(10)).unwrap_err();
+
+        assert_eq!((error.line, error.snippet.as_str()), (2, "45"));
+
+        let error = Grid::parse("123\n4x6\n", |c| c.to_digit(10)).unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt;
+
+/// Half-open range of integers `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Interval {
+    pub fn new(start: usize, end: usize) -> Self {
+        assert!(
+            start <= end,
+            "interval start {} is past its end {}",
+            start,
+            end
+        );
+
+        Interval { start, end }
+    }
+
+    pub fn with_len(start: usize, len: usize) -> Self {
+        let end = start
+            .checked_add(len)
+            .unwrap_or_else(|| panic!("interval of {} from {} overflows", len, start));
+
+        Interval { start, end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        (self.start..self.end).contains(&value)
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+
+        (start < end).then(|| Interval::new(start, end))
+    }
+
+    /// Pieces of `self` left of and right of `other`, if any.
+    pub fn difference(&self, other: &Interval) -> (Option<Interval>, Option<Interval>) {
+        let before = Interval::new(self.start, other.start.clamp(self.start, self.end));
+        let after = Interval::new(other.end.clamp(self.start, self.end), self.end);
+
+        (
+            (!before.is_empty()).then_some(before),
+            (!after.is_empty()).then_some(after),
+        )
+    }
+
+    /// Moves the interval so `from` lands on `to`, keeping its length.
+    pub fn translate(&self, from: usize, to: usize) -> Interval {
+        Interval::new(self.start - from + to, self.end - from + to)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// Sorts `intervals` and coalesces the ones that overlap or touch, dropping
+/// empty ones.
+pub fn merge(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.retain(|interval| !interval.is_empty());
+    intervals.sort();
+
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => merged.push(interval),
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersection_difference_test() {
+        let a = Interval::new(10, 20);
+        let b = Interval::new(15, 30);
+
+        assert_eq!(a.intersection(&b), Some(Interval::new(15, 20)));
+        assert_eq!(a.intersection(&Interval::new(20, 25)), None);
+        assert_eq!(a.difference(&b), (Some(Interval::new(10, 15)), None));
+        assert_eq!(
+            a.difference(&Interval::new(12, 14)),
+            (Some(Interval::new(10, 12)), Some(Interval::new(14, 20)))
+        );
+        assert_eq!(a.translate(10, 50), Interval::new(50, 60));
+    }
+
+    #[test]
+    fn merge_test() {
+        let merged = merge(vec![
+            Interval::new(8, 9),
+            Interval::new(0, 3),
+            Interval::new(5, 5),
+            Interval::new(3, 4),
+            Interval::new(7, 9),
+        ]);
+
+        assert_eq!(merged, vec![Interval::new(0, 4), Interval::new(7, 9)]);
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, BufRead, BufReader, Read},
+    time::Duration,
+};
+
+pub mod answers;
+pub mod cli;
+mod error;
+pub mod grid;
+pub mod interval;
+pub mod timing;
+
+use cli::{exit_on_error, load, print_answers, DayArgs};
+pub use error::{parse_number, ParseError};
+use timing::{bench, time, Bench};
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+
+/// A single Advent of Code day: parse the raw puzzle input once, then solve
+/// both parts from the parsed representation.
+pub trait Solution {
+    type Input;
+    type Output: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Output;
+    fn part2(input: &Self::Input) -> Self::Output;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// Type-erased entry for a day so the runner can drive every solution
+/// without knowing its `Input` and `Output` types.
+pub struct Day {
+    pub number: u32,
+    pub solve: fn(&str, &[Part]) -> Result<Solved, ParseError>,
+    pub bench: fn(&str, &[Part], usize) -> Result<Bench, ParseError>,
+}
+
+impl Day {
+    pub fn input_path(&self) -> String {
+        format!("inputs/day{}.txt", self.number)
+    }
+}
+
+pub static DAYS: [Day; 6] = [
+    Day {
+        number: 1,
+        solve: solve::<day1::Day1>,
+        bench: bench::<day1::Day1>,
+    },
+    Day {
+        number: 2,
+        solve: solve::<day2::Day2>,
+        bench: bench::<day2::Day2>,
+    },
+    Day {
+        number: 3,
+        solve: solve::<day3::Day3>,
+        bench: bench::<day3::Day3>,
+    },
+    Day {
+        number: 4,
+        solve: solve::<day4::Day4>,
+        bench: bench::<day4::Day4>,
+    },
+    Day {
+        number: 5,
+        solve: solve::<day5::Day5>,
+        bench: bench::<day5::Day5>,
+    },
+    Day {
+        number: 6,
+        solve: solve::<day6::Day6>,
+        bench: bench::<day6::Day6>,
+    },
+];
+
+pub fn find_day(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+pub struct Answer {
+    pub part: Part,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+pub struct Solved {
+    pub parse_elapsed: Duration,
+    pub answers: Vec<Answer>,
+}
+
+pub fn solve<S: Solution>(input: &str, parts: &[Part]) -> Result<Solved, ParseError> {
+    let (input, parse_elapsed) = time(|| S::parse(input));
+    let input = input?;
+
+    let answers = parts
+        .iter()
+        .map(|part| {
+            let (value, elapsed) = time(|| match part {
+                Part::One => S::part1(&input),
+                Part::Two => S::part2(&input),
+            });
+
+            Answer {
+                part: *part,
+                value: value.to_string(),
+                elapsed,
+            }
+        })
+        .collect();
+
+    Ok(Solved {
+        parse_elapsed,
+        answers,
+    })
+}
+
+/// Input path that reads the puzzle input from stdin instead of a file.
+pub const STDIN: &str = "-";
+
+/// Name to report in errors for input read from `path`.
+pub fn input_name(path: &str) -> &str {
+    if path == STDIN {
+        "<stdin>"
+    } else {
+        path
+    }
+}
+
+pub fn read_input(path: &str) -> io::Result<String> {
+    if path == STDIN {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+
+        return Ok(input);
+    }
+
+    fs::read_to_string(path)
+}
+
+/// Like `read_input`, but hands out the input a line at a time instead of
+/// loading it whole.
+pub fn open_input(path: &str) -> io::Result<Box<dyn BufRead>> {
+    if path == STDIN {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+
+    Ok(Box::new(BufReader::new(fs::File::open(path)?)))
+}
+
+/// Entry point for the `dayX` binaries: reads `default_path` unless an
+/// `--input <path|->` argument overrides it.
+pub fn run<S: Solution>(default_path: &str) {
+    let args = DayArgs::from_env(default_path, &[], &[]);
+    let input = exit_on_error(load::<S>(&args.input));
+
+    print_answers::<S>(&input);
+}
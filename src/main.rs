@@ -1,3 +1,350 @@
+use std::{env, process};
+
+use aoc23::{
+    answers::{self, Answers, ANSWERS_PATH},
+    cli::load_with,
+    find_day,
+    timing::{Phase, Samples},
+    Day, Part, Solved, DAYS,
+};
+
+const USAGE: &str = "usage: aoc23 run <day|all> [--part <1|2>] [--input <path|->] [--time]
+       aoc23 bench <day|all> [--part <1|2>] [--input <path|->] [--runs <n>]
+       aoc23 verify <day|all> [--part <1|2>]";
+
+const DEFAULT_RUNS: usize = 10;
+
+enum Command {
+    Run { time: bool },
+    Bench { runs: usize },
+    Verify,
+}
+
+enum Selection {
+    Single(&'static Day),
+    All,
+}
+
+struct Args {
+    command: Command,
+    selection: Selection,
+    parts: Vec<Part>,
+    input: Option<String>,
+}
+
 fn main() {
-    println!("Hello, world!");
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    };
+
+    let days: Vec<(&Day, String)> = match args.selection {
+        Selection::Single(day) => vec![(day, args.input.unwrap_or_else(|| day.input_path()))],
+        Selection::All => DAYS.iter().map(|day| (day, day.input_path())).collect(),
+    };
+
+    let result = match (args.command, days.len()) {
+        (Command::Run { time }, 1) => run_day(&days[0], &args.parts, time),
+        (Command::Run { time }, _) => run_all(&days, &args.parts, time),
+        (Command::Bench { runs }, _) => bench(&days, &args.parts, runs),
+        (Command::Verify, _) => verify(&days, &args.parts),
+    };
+
+    if let Err(message) = result {
+        eprintln!("error: {}", message);
+        process::exit(1);
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut command = match args.next().as_deref() {
+        Some("run") => Command::Run { time: false },
+        Some("bench") => Command::Bench { runs: DEFAULT_RUNS },
+        Some("verify") => Command::Verify,
+        Some(command) => return Err(format!("unknown command '{}'", command)),
+        None => return Err("missing command".to_string()),
+    };
+
+    let selection = match args.next().as_deref() {
+        Some("all") => Selection::All,
+        Some(day) => {
+            let number = day
+                .parse::<u32>()
+                .map_err(|_| format!("invalid day '{}'", day))?;
+
+            Selection::Single(find_day(number).ok_or(format!("day {} is not solved yet", number))?)
+        }
+        None => return Err("missing day".to_string()),
+    };
+
+    let mut parts = Part::BOTH.to_vec();
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), &mut command) {
+            ("--part", _) => {
+                parts = match args.next().as_deref() {
+                    Some("1") => vec![Part::One],
+                    Some("2") => vec![Part::Two],
+                    Some(part) => return Err(format!("invalid part '{}'", part)),
+                    None => return Err("missing value for --part".to_string()),
+                }
+            }
+            ("--input", Command::Run { .. } | Command::Bench { .. }) => {
+                input = Some(args.next().ok_or("missing value for --input")?);
+            }
+            ("--time", Command::Run { time }) => *time = true,
+            ("--runs", Command::Bench { runs }) => {
+                *runs = match args.next().map(|n| n.parse::<usize>()) {
+                    Some(Ok(n)) if n > 0 => n,
+                    Some(_) => return Err("--runs expects a positive number".to_string()),
+                    None => return Err("missing value for --runs".to_string()),
+                }
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    if input.is_some() && matches!(selection, Selection::All) {
+        return Err("--input can only be used when running a single day".to_string());
+    }
+
+    Ok(Args {
+        command,
+        selection,
+        parts,
+        input,
+    })
+}
+
+fn solve((day, path): &(&Day, String), parts: &[Part]) -> Result<Solved, String> {
+    load_with(path, |input| (day.solve)(input, parts))
+}
+
+fn run_day(day: &(&Day, String), parts: &[Part], time: bool) -> Result<(), String> {
+    let solved = solve(day, parts)?;
+
+    if time {
+        println!("Parse: {:.2?}", solved.parse_elapsed);
+    }
+
+    for answer in solved.answers {
+        if time {
+            println!(
+                "Puzzle #{}: {} ({:.2?})",
+                answer.part.number(),
+                answer.value,
+                answer.elapsed
+            );
+        } else {
+            println!("Puzzle #{}: {}", answer.part.number(), answer.value);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_all(days: &[(&Day, String)], parts: &[Part], time: bool) -> Result<(), String> {
+    let mut headers = vec!["Day".to_string()];
+
+    if time {
+        headers.push("Parse".to_string());
+    }
+
+    for part in parts {
+        headers.push(format!("Puzzle #{}", part.number()));
+
+        if time {
+            headers.push(format!("Time #{}", part.number()));
+        }
+    }
+
+    let mut rows: Vec<Vec<String>> = vec![];
+
+    for day in days {
+        let solved = solve(day, parts)?;
+        let mut row = vec![day.0.number.to_string()];
+
+        if time {
+            row.push(format!("{:.2?}", solved.parse_elapsed));
+        }
+
+        for answer in solved.answers {
+            row.push(answer.value);
+
+            if time {
+                row.push(format!("{:.2?}", answer.elapsed));
+            }
+        }
+
+        rows.push(row);
+    }
+
+    print_table(&headers, &rows);
+
+    Ok(())
+}
+
+fn bench(days: &[(&Day, String)], parts: &[Part], runs: usize) -> Result<(), String> {
+    let headers = ["Day", "Phase", "Min", "Median", "P95"].map(String::from);
+    let mut rows: Vec<Vec<String>> = vec![];
+
+    for (day, path) in days {
+        let phases = load_with(path, |input| (day.bench)(input, parts, runs))?;
+
+        for (phase, samples) in phases {
+            rows.push(bench_row(day.number, phase, &samples));
+        }
+    }
+
+    println!("{} runs per phase", runs);
+    print_table(&headers, &rows);
+
+    Ok(())
+}
+
+fn verify(days: &[(&Day, String)], parts: &[Part]) -> Result<(), String> {
+    let answers = load_with(ANSWERS_PATH, Answers::parse)?;
+
+    let headers = ["Day", "Part", "Expected", "Actual", "Status"].map(String::from);
+    let mut rows: Vec<Vec<String>> = vec![];
+    let mut failures = 0;
+
+    for (day, path) in days {
+        let verdicts = load_with(path, |input| answers::verify(&answers, day, input, parts))?;
+
+        for verdict in verdicts {
+            let status = match (&verdict.expected, verdict.is_match()) {
+                (None, _) => "missing",
+                (Some(_), true) => "ok",
+                (Some(_), false) => "MISMATCH",
+            };
+
+            if !verdict.is_match() {
+                failures += 1;
+            }
+
+            rows.push(vec![
+                verdict.day.to_string(),
+                verdict.part.number().to_string(),
+                verdict.expected.unwrap_or_default(),
+                verdict.actual,
+                status.to_string(),
+            ]);
+        }
+    }
+
+    print_table(&headers, &rows);
+
+    match failures {
+        0 => Ok(()),
+        _ => Err(format!(
+            "{} answer(s) do not match {}",
+            failures, ANSWERS_PATH
+        )),
+    }
+}
+
+fn bench_row(day: u32, phase: Phase, samples: &Samples) -> Vec<String> {
+    let phase = match phase {
+        Phase::Parse => "Parse".to_string(),
+        Phase::Solve(part) => format!("Puzzle #{}", part.number()),
+    };
+
+    vec![
+        day.to_string(),
+        phase,
+        format!("{:.2?}", samples.min()),
+        format!("{:.2?}", samples.median()),
+        format!("{:.2?}", samples.p95()),
+    ]
+}
+
+fn print_table(headers: &[String], rows: &[Vec<String>]) {
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([header.len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let format_row = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join(" | ")
+    };
+
+    println!("{}", format_row(headers));
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<String>>()
+            .join("-+-")
+    );
+
+    for row in rows {
+        println!("{}", format_row(row));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Result<Args, String> {
+        parse_args(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn parse_args_test() {
+        let parsed = args("run 5 --part 2 --input - --time").unwrap();
+
+        assert!(matches!(parsed.command, Command::Run { time: true }));
+        assert!(matches!(parsed.selection, Selection::Single(day) if day.number == 5));
+        assert_eq!(parsed.parts, vec![Part::Two]);
+        assert_eq!(parsed.input.as_deref(), Some("-"));
+
+        let parsed = args("run all").unwrap();
+
+        assert!(matches!(parsed.command, Command::Run { time: false }));
+        assert!(matches!(parsed.selection, Selection::All));
+        assert_eq!(parsed.parts, Part::BOTH.to_vec());
+
+        let parsed = args("bench 3 --runs 50").unwrap();
+
+        assert!(matches!(parsed.command, Command::Bench { runs: 50 }));
+
+        let parsed = args("verify all --part 1").unwrap();
+
+        assert!(matches!(parsed.command, Command::Verify));
+        assert_eq!(parsed.parts, vec![Part::One]);
+    }
+
+    #[test]
+    fn parse_args_errors_test() {
+        assert!(args("").is_err());
+        assert!(args("walk 1").is_err());
+        assert!(args("run 26").is_err());
+        assert!(args("run 1 --part 3").is_err());
+        assert!(args("run 1 --input").is_err());
+        assert!(args("run all --input inputs/day1.txt").is_err());
+        assert!(args("run 1 --runs 5").is_err());
+        assert!(args("bench 1 --runs 0").is_err());
+        assert!(args("bench 1 --time").is_err());
+        assert!(args("verify 1 --input -").is_err());
+    }
 }
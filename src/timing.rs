@@ -0,0 +1,92 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{ParseError, Part, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+/// Wall times of every run of a phase, sorted from fastest to slowest.
+#[derive(Debug, Clone)]
+pub struct Samples(Vec<Duration>);
+
+impl Samples {
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "a phase needs at least one run");
+
+        samples.sort();
+        Samples(samples)
+    }
+
+    pub fn min(&self) -> Duration {
+        self.0[0]
+    }
+
+    pub fn median(&self) -> Duration {
+        self.0[(self.0.len() - 1) / 2]
+    }
+
+    /// Nearest-rank 95th percentile.
+    pub fn p95(&self) -> Duration {
+        let rank = (self.0.len() * 95).div_ceil(100);
+
+        self.0[rank - 1]
+    }
+}
+
+/// Samples of every benchmarked phase, in the order they ran.
+pub type Bench = Vec<(Phase, Samples)>;
+
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+
+    (value, start.elapsed())
+}
+
+/// Runs parsing and each of `parts` `runs` times, every phase on its own so a
+/// slow part does not hide behind a fast one.
+pub fn bench<S: Solution>(input: &str, parts: &[Part], runs: usize) -> Result<Bench, ParseError> {
+    let parsed = S::parse(input)?;
+
+    let mut phases = vec![(
+        Phase::Parse,
+        repeat(runs, || {
+            let _ = black_box(S::parse(black_box(input)));
+        }),
+    )];
+
+    for part in parts {
+        let samples = repeat(runs, || match part {
+            Part::One => drop(black_box(S::part1(black_box(&parsed)))),
+            Part::Two => drop(black_box(S::part2(black_box(&parsed)))),
+        });
+
+        phases.push((Phase::Solve(*part), samples));
+    }
+
+    Ok(phases)
+}
+
+fn repeat(runs: usize, mut f: impl FnMut()) -> Samples {
+    Samples::new((0..runs).map(|_| time(&mut f).1).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_test() {
+        let samples = Samples::new((1..=20).rev().map(Duration::from_millis).collect());
+
+        assert_eq!(samples.min(), Duration::from_millis(1));
+        assert_eq!(samples.median(), Duration::from_millis(10));
+        assert_eq!(samples.p95(), Duration::from_millis(19));
+    }
+}